@@ -0,0 +1,139 @@
+/// A token of an AQL string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AqlToken {
+    /// Keyword or identifier
+    Word(String),
+    /// Backtick or forward tick quoted identifier
+    QuotedWord(String),
+    /// `@name` bind parameter
+    BindVar(String),
+    /// `@@name` collection bind parameter
+    CollectionBindVar(String),
+    /// String literal
+    Str,
+    /// Number literal
+    Number,
+    /// Operator or punctuation character
+    Symbol(char),
+}
+
+impl AqlToken {
+    /// Returns `true` if the token is the `keyword`, case insensitive
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Self::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    /// The name of the identifier tokens
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::Word(name) | Self::QuotedWord(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+const fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '$'
+}
+
+const fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Splits `aql` in tokens, checking the strings, comments and brackets are closed
+pub fn tokenize(aql: &str) -> Result<Vec<AqlToken>, String> {
+    let chars: Vec<char> = aql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut brackets = Vec::new();
+    let mut i = 0;
+    let take_while = |start: usize, predicate: fn(char) -> bool| {
+        let mut end = start;
+        while end < chars.len() && predicate(chars[end]) {
+            end += 1;
+        }
+        end
+    };
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            let end = (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .ok_or("Unterminated `/*` comment")?;
+            i = end + 2;
+        } else if c == '\'' || c == '"' || c == '`' || c == '´' {
+            let mut end = i + 1;
+            while end < chars.len() && chars[end] != c {
+                // Escaped characters
+                end += if chars[end] == '\\' { 2 } else { 1 };
+            }
+            if end >= chars.len() {
+                return Err(format!("Unterminated `{}` quoted string", c));
+            }
+            tokens.push(if c == '\'' || c == '"' {
+                AqlToken::Str
+            } else {
+                AqlToken::QuotedWord(chars[i + 1..end].iter().collect())
+            });
+            i = end + 1;
+        } else if c == '@' {
+            let (start, collection) = if next == Some('@') {
+                (i + 2, true)
+            } else {
+                (i + 1, false)
+            };
+            let end = take_while(start, is_identifier_char);
+            if end == start {
+                return Err("Expected a bind parameter name after `@`".to_string());
+            }
+            let name = chars[start..end].iter().collect();
+            tokens.push(if collection {
+                AqlToken::CollectionBindVar(name)
+            } else {
+                AqlToken::BindVar(name)
+            });
+            i = end;
+        } else if is_identifier_start(c) {
+            let end = take_while(i, is_identifier_char);
+            tokens.push(AqlToken::Word(chars[i..end].iter().collect()));
+            i = end;
+        } else if c.is_ascii_digit() {
+            let mut end = take_while(i, |c| c.is_ascii_alphanumeric());
+            // Decimal part, which must not be confused with a `..` range
+            if chars.get(end) == Some(&'.')
+                && chars.get(end + 1).map_or(false, char::is_ascii_digit)
+            {
+                end = take_while(end + 1, |c| c.is_ascii_alphanumeric());
+            }
+            tokens.push(AqlToken::Number);
+            i = end;
+        } else {
+            match c {
+                '(' | '[' | '{' => brackets.push(c),
+                ')' | ']' | '}' => {
+                    let expected = match c {
+                        ')' => '(',
+                        ']' => '[',
+                        _ => '{',
+                    };
+                    if brackets.pop() != Some(expected) {
+                        return Err(format!("Unexpected `{}`", c));
+                    }
+                }
+                _ => (),
+            }
+            tokens.push(AqlToken::Symbol(c));
+            i += 1;
+        }
+    }
+    if let Some(bracket) = brackets.pop() {
+        return Err(format!("Unclosed `{}`", bracket));
+    }
+    Ok(tokens)
+}
@@ -0,0 +1,311 @@
+use std::collections::HashSet;
+
+use proc_macro::TokenStream;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, ExprPath, Ident, LitStr, Token, Type};
+
+use crate::aql::lexer::{tokenize, AqlToken};
+use crate::aql::schema::AqlSchema;
+
+mod lexer;
+mod schema;
+
+/// The keywords an AQL query can start with
+const FIRST_KEYWORDS: [&str; 9] = [
+    "FOR", "LET", "RETURN", "WITH", "INSERT", "UPDATE", "REPLACE", "REMOVE", "UPSERT",
+];
+/// The data modification keywords, followed by an `IN` or `INTO` collection
+const MODIFICATION_KEYWORDS: [&str; 5] = ["INSERT", "UPDATE", "REPLACE", "REMOVE", "UPSERT"];
+/// The graph traversal directions, the traversed edge collections are not checked
+const TRAVERSAL_KEYWORDS: [&str; 3] = ["OUTBOUND", "INBOUND", "ANY"];
+
+/// A `name = value`, `name: Type = value` or `name` argument of the macro
+struct AqlArgument {
+    /// `true` for the `@name` arguments binding `@@name` collection parameters
+    collection: bool,
+    name: Ident,
+    ty: Option<Type>,
+    value: Expr,
+}
+
+impl AqlArgument {
+    /// The bind variable key
+    fn key(&self) -> String {
+        if self.collection {
+            format!("@{}", self.name)
+        } else {
+            self.name.to_string()
+        }
+    }
+}
+
+impl Parse for AqlArgument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let collection = input.parse::<Option<Token![@]>>()?.is_some();
+        let name: Ident = input.parse()?;
+        let ty = if input.parse::<Option<Token![:]>>()?.is_some() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let value = if input.parse::<Option<Token![=]>>()?.is_some() {
+            input.parse()?
+        } else {
+            // Shorthand argument, using the variable of the same name
+            Expr::Path(ExprPath {
+                attrs: Vec::new(),
+                qself: None,
+                path: name.clone().into(),
+            })
+        };
+        Ok(Self {
+            collection,
+            name,
+            ty,
+            value,
+        })
+    }
+}
+
+/// The `aql!` input: the AQL string literal and its bind parameter arguments
+struct AqlInput {
+    aql: LitStr,
+    arguments: Vec<AqlArgument>,
+}
+
+impl Parse for AqlInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let aql = input.parse()?;
+        let mut arguments = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            arguments.push(input.parse()?);
+        }
+        Ok(Self { aql, arguments })
+    }
+}
+
+/// Returns `true` if the token at `index` is the `keyword`, and not an attribute name or an
+/// object key
+fn is_keyword_at(tokens: &[AqlToken], index: usize, keyword: &str) -> bool {
+    let is_attribute = (index > 0 && tokens[index - 1] == AqlToken::Symbol('.'))
+        || tokens.get(index + 1) == Some(&AqlToken::Symbol(':'));
+    !is_attribute && tokens.get(index).map_or(false, |t| t.is_keyword(keyword))
+}
+
+fn is_any_keyword_at(tokens: &[AqlToken], index: usize, keywords: &[&str]) -> bool {
+    keywords
+        .iter()
+        .any(|keyword| is_keyword_at(tokens, index, keyword))
+}
+
+/// Collects the declared variables: `FOR` variables, `LET`, `COLLECT` and `AGGREGATE`
+/// assignments and `INTO` groups
+fn declared_variables(tokens: &[AqlToken]) -> HashSet<String> {
+    let mut variables = HashSet::new();
+    let mut collecting = false;
+    for (i, token) in tokens.iter().enumerate() {
+        if is_keyword_at(tokens, i, "FOR") {
+            collecting = false;
+            let mut j = i + 1;
+            while let Some(AqlToken::Word(name)) = tokens.get(j) {
+                if is_keyword_at(tokens, j, "IN") {
+                    break;
+                }
+                variables.insert(name.clone());
+                if tokens.get(j + 1) != Some(&AqlToken::Symbol(',')) {
+                    break;
+                }
+                j += 2;
+            }
+        } else if is_keyword_at(tokens, i, "COLLECT") {
+            collecting = true;
+        } else if collecting && is_keyword_at(tokens, i, "INTO") {
+            if let Some(AqlToken::Word(name)) = tokens.get(i + 1) {
+                variables.insert(name.clone());
+            }
+        } else if let AqlToken::Word(name) = token {
+            // `name = value` assignments, not `==` comparisons
+            let assigned = tokens.get(i + 1) == Some(&AqlToken::Symbol('='))
+                && tokens.get(i + 2) != Some(&AqlToken::Symbol('='));
+            if assigned {
+                variables.insert(name.clone());
+            }
+        }
+    }
+    variables
+}
+
+/// Collects the collections used by the query: the `FOR ... IN` collections, the `WITH`
+/// collections and the data modification collections
+fn used_collections(tokens: &[AqlToken]) -> Vec<String> {
+    let variables = declared_variables(tokens);
+    let mut collections = Vec::new();
+    if is_keyword_at(tokens, 0, "WITH") {
+        let mut j = 1;
+        while let Some(name) = tokens.get(j).and_then(AqlToken::name) {
+            collections.push(name.to_string());
+            if tokens.get(j + 1) != Some(&AqlToken::Symbol(',')) {
+                break;
+            }
+            j += 2;
+        }
+    }
+    let mut depth = 0_usize;
+    // Bracket depth of the data modification waiting for its collection
+    let mut modification: Option<usize> = None;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            AqlToken::Symbol('(' | '[' | '{') => depth += 1,
+            AqlToken::Symbol(')' | ']' | '}') => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+        if is_any_keyword_at(tokens, i, &MODIFICATION_KEYWORDS) {
+            modification.get_or_insert(depth);
+        } else if modification == Some(depth) && is_any_keyword_at(tokens, i, &["IN", "INTO"]) {
+            modification = None;
+            if let Some(name) = tokens.get(i + 1).and_then(AqlToken::name) {
+                collections.push(name.to_string());
+            }
+        } else if is_keyword_at(tokens, i, "FOR") {
+            let mut j = i + 1;
+            while j < tokens.len() && !is_keyword_at(tokens, j, "IN") {
+                j += 1;
+            }
+            let name = match tokens.get(j + 1) {
+                Some(AqlToken::Word(name)) => name,
+                Some(AqlToken::QuotedWord(name)) => {
+                    collections.push(name.clone());
+                    continue;
+                }
+                _ => continue,
+            };
+            let is_expression =
+                matches!(tokens.get(j + 2), Some(AqlToken::Symbol('.' | '[' | '(')));
+            let is_view = is_keyword_at(tokens, j + 2, "SEARCH");
+            if !is_expression
+                && !is_view
+                && !variables.contains(name)
+                && !is_any_keyword_at(tokens, j + 1, &TRAVERSAL_KEYWORDS)
+            {
+                collections.push(name.clone());
+            }
+        }
+    }
+    collections
+}
+
+/// Checks the AQL of the macro input, returns the loaded schema if any
+fn check_aql(input: &AqlInput) -> Option<AqlSchema> {
+    let span = input.aql.span();
+    let tokens = match tokenize(&input.aql.value()) {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            emit_error!(span, "Invalid AQL: {}", error);
+            return None;
+        }
+    };
+    if !is_any_keyword_at(&tokens, 0, &FIRST_KEYWORDS) {
+        emit_error!(
+            span,
+            "Invalid AQL: the query must start with one of {}",
+            FIRST_KEYWORDS.join(", ")
+        );
+    }
+    let has_result = (0..tokens.len()).any(|i| {
+        is_keyword_at(&tokens, i, "RETURN") || is_any_keyword_at(&tokens, i, &MODIFICATION_KEYWORDS)
+    });
+    if !has_result {
+        emit_error!(
+            span,
+            "Invalid AQL: the query must contain a RETURN or a data modification operation"
+        );
+    }
+    // Bind parameters
+    let mut parameters = HashSet::new();
+    for token in &tokens {
+        match token {
+            AqlToken::BindVar(name) => parameters.insert(name.clone()),
+            AqlToken::CollectionBindVar(name) => parameters.insert(format!("@{}", name)),
+            _ => false,
+        };
+    }
+    let mut keys = HashSet::new();
+    for argument in &input.arguments {
+        let key = argument.key();
+        if !keys.insert(key.clone()) {
+            emit_error!(
+                argument.name.span(),
+                "Duplicate `{}` argument",
+                argument.name
+            );
+        } else if !parameters.contains(&key) {
+            emit_error!(
+                argument.name.span(),
+                "Unused argument, the query has no `@{}` bind parameter",
+                key
+            );
+        }
+    }
+    let mut missing: Vec<&String> = parameters.difference(&keys).collect();
+    missing.sort();
+    for parameter in missing {
+        let argument = parameter.trim_start_matches('@');
+        emit_error!(
+            span,
+            "Missing argument for the `@{}` bind parameter, add `{}{} = value` to the macro",
+            parameter,
+            if parameter.starts_with('@') { "@" } else { "" },
+            argument
+        );
+    }
+    // Collections
+    let schema = match AqlSchema::load() {
+        Ok(schema) => schema?,
+        Err(error) => {
+            emit_error!(span, "{}", error);
+            return None;
+        }
+    };
+    for collection in used_collections(&tokens) {
+        if !schema.has_collection(&collection) {
+            emit_error!(
+                span,
+                "Unknown collection `{}`, missing from the schema {}",
+                collection,
+                schema.path.display()
+            );
+        }
+    }
+    Some(schema)
+}
+
+pub fn impl_aql_macro(input: TokenStream) -> TokenStream {
+    let input: AqlInput = match syn::parse(input) {
+        Ok(input) => input,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    // The schema file is included so that its changes trigger a new check
+    let schema_dependency = check_aql(&input).map(|schema| {
+        let path = schema.path.to_string_lossy().to_string();
+        quote! { const _: &str = include_str!(#path); }
+    });
+    let aql = &input.aql;
+    let bindings = input.arguments.iter().map(|argument| {
+        let key = argument.key();
+        let value = &argument.value;
+        let value = argument.ty.as_ref().map_or_else(
+            || quote! { #value },
+            |ty| quote! {{ let value: #ty = #value; value }},
+        );
+        quote! { .bind_var(#key, #value) }
+    });
+    let res = quote! {{
+        #schema_dependency
+        aragog::query::Query::raw(#aql) #(#bindings)*
+    }};
+    res.into()
+}
@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Default schema path, relative to the crate root, as used by `DatabaseConnection`
+const SCHEMA_DEFAULT_PATH: &str = "src/config/db";
+/// Default schema file name
+const SCHEMA_DEFAULT_FILE_NAME: &str = "schema.yaml";
+
+#[derive(Deserialize)]
+struct CollectionName {
+    name: String,
+}
+
+/// The part of the database schema used to check the `aql!` collections
+#[derive(Deserialize)]
+pub struct AqlSchema {
+    /// The schema file path
+    #[serde(skip)]
+    pub path: PathBuf,
+    #[serde(default)]
+    collections: Vec<CollectionName>,
+}
+
+impl AqlSchema {
+    /// Resolves the schema file from the `SCHEMA_PATH` env var, which may be either the schema
+    /// directory or the schema file, or from the default path
+    fn schema_path() -> PathBuf {
+        let path = std::env::var("SCHEMA_PATH").unwrap_or_else(|_| SCHEMA_DEFAULT_PATH.to_string());
+        let mut path = PathBuf::from(path);
+        if !matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("yaml" | "yml")
+        ) {
+            path.push(SCHEMA_DEFAULT_FILE_NAME);
+        }
+        match std::env::var("CARGO_MANIFEST_DIR") {
+            Ok(root) if path.is_relative() => Path::new(&root).join(path),
+            _ => path,
+        }
+    }
+
+    /// Loads the configured schema, returns `None` if there is no schema file
+    pub fn load() -> Result<Option<Self>, String> {
+        let path = Self::schema_path();
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+        let mut schema: Self = serde_yaml::from_str(&content)
+            .map_err(|error| format!("Failed to parse {}: {}", path.display(), error))?;
+        schema.path = path;
+        Ok(Some(schema))
+    }
+
+    /// Returns `true` if `name` is a collection of the schema
+    pub fn has_collection(&self, name: &str) -> bool {
+        self.collections
+            .iter()
+            .any(|collection| collection.name == name)
+    }
+}
@@ -0,0 +1,112 @@
+use crate::derives::fields::serde_attribute::{apply_rename_all, rename_all_rule, SerdeField};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use syn::{Data, Fields, Ident};
+
+mod serde_attribute;
+
+/// Attribute marking a field whose type implements `Fields`, to access its nested paths
+const NESTED_FIELDS_ATTRIBUTE: &str = "nested_fields";
+
+pub fn impl_fields_macro(ast: &syn::DeriveInput) -> TokenStream {
+    fields_token_stream(ast).map_or_else(
+        || {
+            emit_call_site_error!("`Fields` only supports structs with named fields");
+            TokenStream::new()
+        },
+        Into::into,
+    )
+}
+
+/// Renders the field paths struct of `ast` and its `Fields` implementation, if `ast` is a
+/// struct with named fields
+pub fn fields_token_stream(ast: &syn::DeriveInput) -> Option<proc_macro2::TokenStream> {
+    let target_name = &ast.ident;
+    let vis = &ast.vis;
+    let fields = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let rename_all = rename_all_rule(&ast.attrs);
+    let mut accessors = Vec::new();
+    for field in fields {
+        let serde = SerdeField::parse(&field.attrs);
+        if serde.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref()?;
+        let field_type = &field.ty;
+        // The accessors are as visible as their fields
+        let field_vis = &field.vis;
+        let ident_str = ident.to_string();
+        let ident_str = ident_str.trim_start_matches("r#");
+        let name = serde.rename.unwrap_or_else(|| {
+            rename_all.as_ref().map_or_else(
+                || ident_str.to_string(),
+                |rule| apply_rename_all(rule, ident_str),
+            )
+        });
+        let nested = field
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident(NESTED_FIELDS_ATTRIBUTE));
+        let accessor = if serde.flatten {
+            let doc = format!("Paths of the flattened `{}` fields", ident_str);
+            quote! {
+                #[doc = #doc]
+                #[must_use]
+                #field_vis fn #ident(&self) -> <#field_type as aragog::query::Fields>::Paths {
+                    <#field_type as aragog::query::Fields>::field_paths(&self.prefix)
+                }
+            }
+        } else if nested {
+            let doc = format!("Paths of the `{}` object fields", name);
+            quote! {
+                #[doc = #doc]
+                #[must_use]
+                #field_vis fn #ident(&self) -> <#field_type as aragog::query::Fields>::Paths {
+                    <#field_type as aragog::query::Fields>::field_paths(
+                        aragog::query::Field::<#field_type>::nested(&self.prefix, #name).path()
+                    )
+                }
+            }
+        } else {
+            let doc = format!("Path of the `{}` field", name);
+            quote! {
+                #[doc = #doc]
+                #[must_use]
+                #field_vis fn #ident(&self) -> aragog::query::Field<#field_type> {
+                    aragog::query::Field::nested(&self.prefix, #name)
+                }
+            }
+        };
+        accessors.push(accessor);
+    }
+    let paths_name = Ident::new(&format!("{}Fields", target_name), Span::call_site());
+    let doc = format!("Typed field paths of [`{}`]", target_name);
+    Some(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Debug)]
+        #vis struct #paths_name {
+            prefix: String,
+        }
+
+        #[allow(dead_code)]
+        impl #paths_name {
+            #(#accessors)*
+        }
+
+        impl aragog::query::Fields for #target_name {
+            type Paths = #paths_name;
+
+            fn field_paths(prefix: &str) -> Self::Paths {
+                #paths_name {
+                    prefix: prefix.to_string(),
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,112 @@
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+/// The serde attributes of a field changing its serialized path
+#[derive(Default)]
+pub struct SerdeField {
+    pub rename: Option<String>,
+    pub flatten: bool,
+    pub skip: bool,
+}
+
+/// Iterates over the items of the `#[serde(...)]` attributes
+fn serde_metas(attrs: &[Attribute]) -> Vec<Meta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested.into_iter()),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(meta) => Some(meta),
+            NestedMeta::Lit(_) => None,
+        })
+        .collect()
+}
+
+/// Retrieves the serialized value of a `name = "value"` or `name(serialize = "value")` item
+fn serialized_value(meta: &Meta, name: &str) -> Option<String> {
+    match meta {
+        Meta::NameValue(named_value) if named_value.path.is_ident(name) => match &named_value.lit {
+            Lit::Str(value) => Some(value.value()),
+            _ => None,
+        },
+        Meta::List(list) if list.path.is_ident(name) => {
+            list.nested.iter().find_map(|nested| match nested {
+                NestedMeta::Meta(Meta::NameValue(named_value))
+                    if named_value.path.is_ident("serialize") =>
+                {
+                    match &named_value.lit {
+                        Lit::Str(value) => Some(value.value()),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+impl SerdeField {
+    pub fn parse(attrs: &[Attribute]) -> Self {
+        let mut res = Self::default();
+        for meta in serde_metas(attrs) {
+            if let Some(rename) = serialized_value(&meta, "rename") {
+                res.rename = Some(rename);
+            } else if let Meta::Path(path) = &meta {
+                if path.is_ident("flatten") {
+                    res.flatten = true;
+                } else if path.is_ident("skip") || path.is_ident("skip_serializing") {
+                    res.skip = true;
+                }
+            }
+        }
+        res
+    }
+}
+
+/// Retrieves the container `rename_all` rule
+pub fn rename_all_rule(attrs: &[Attribute]) -> Option<String> {
+    serde_metas(attrs)
+        .iter()
+        .find_map(|meta| serialized_value(meta, "rename_all"))
+}
+
+/// Renames a snake case `field` following the serde `rename_all` rule
+pub fn apply_rename_all(rule: &str, field: &str) -> String {
+    let pascal_case = || {
+        let mut res = String::new();
+        let mut capitalize = true;
+        for c in field.chars() {
+            if c == '_' {
+                capitalize = true;
+            } else if capitalize {
+                res.push(c.to_ascii_uppercase());
+                capitalize = false;
+            } else {
+                res.push(c);
+            }
+        }
+        res
+    };
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_ascii_uppercase(),
+        "PascalCase" => pascal_case(),
+        "camelCase" => {
+            let pascal = pascal_case();
+            let mut chars = pascal.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_lowercase().to_string() + chars.as_str()
+            })
+        }
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.to_ascii_uppercase().replace('_', "-"),
+        "lowercase" | "snake_case" => field.to_string(),
+        _ => {
+            emit_call_site_error!("Unknown serde `rename_all` rule `{}`", rule);
+            field.to_string()
+        }
+    }
+}
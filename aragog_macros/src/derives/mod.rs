@@ -1,4 +1,5 @@
-pub use {record::impl_record_macro, validate::impl_validate_macro};
+pub use {fields::impl_fields_macro, record::impl_record_macro, validate::impl_validate_macro};
 
+mod fields;
 mod record;
 mod validate;
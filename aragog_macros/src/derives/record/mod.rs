@@ -1,3 +1,4 @@
+use crate::derives::fields::fields_token_stream;
 use crate::derives::record::collection_attribute::CollectionNameAttribute;
 use crate::derives::record::hook::Hook;
 use crate::derives::record::hooks_container::HooksContainer;
@@ -35,6 +36,8 @@ pub fn impl_record_macro(ast: &syn::DeriveInput) -> TokenStream {
     };
     let container = HooksContainer::from(hooks);
     let container_quote = container.token_stream();
+    // Tuple and unit structs have no field paths
+    let fields_quote = fields_token_stream(ast).unwrap_or_default();
     #[cfg(feature = "blocking")]
     let gen = quote! {
         impl Record for #target_name {
@@ -42,6 +45,8 @@ pub fn impl_record_macro(ast: &syn::DeriveInput) -> TokenStream {
 
             #container_quote
         }
+
+        #fields_quote
     };
     #[cfg(not(feature = "blocking"))]
     let gen = quote! {
@@ -51,6 +56,8 @@ pub fn impl_record_macro(ast: &syn::DeriveInput) -> TokenStream {
 
             #container_quote
         }
+
+        #fields_quote
     };
     // Debug purpose
     // println!("{}", gen);
@@ -10,8 +10,10 @@ use proc_macro::TokenStream;
 
 use syn::{self, DeriveInput};
 
-use crate::derives::{impl_record_macro, impl_validate_macro};
+use crate::aql::impl_aql_macro;
+use crate::derives::{impl_fields_macro, impl_record_macro, impl_validate_macro};
 
+mod aql;
 mod derives;
 mod parse_attribute;
 mod parse_operation;
@@ -33,6 +35,7 @@ mod toolbox;
         after_delete,
         after_write,
         after_all,
+        nested_fields,
     )
 )]
 pub fn record_macro_derive(attr: TokenStream) -> TokenStream {
@@ -54,3 +57,21 @@ pub fn validate_macro_derive(attr: TokenStream) -> TokenStream {
     // Build the trait implementation
     impl_validate_macro(&ast)
 }
+
+#[proc_macro_error]
+#[proc_macro_derive(Fields, attributes(nested_fields))]
+pub fn fields_macro_derive(attr: TokenStream) -> TokenStream {
+    // Construct a representation of Rust code as a syntax tree
+    // that we can manipulate
+    let ast: DeriveInput = syn::parse(attr).unwrap();
+
+    // Build the field paths
+    impl_fields_macro(&ast)
+}
+
+#[proc_macro_error]
+#[proc_macro]
+pub fn aql(input: TokenStream) -> TokenStream {
+    // Check the AQL string and bind its parameters
+    impl_aql_macro(input)
+}
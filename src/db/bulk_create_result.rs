@@ -0,0 +1,25 @@
+use crate::{DatabaseRecord, Error};
+
+/// Result of a bulk document creation, as returned by [`DatabaseRecord`]::[`create_many`]
+///
+/// [`DatabaseRecord`]: crate::DatabaseRecord
+/// [`create_many`]: crate::DatabaseRecord::create_many
+#[derive(Debug)]
+pub struct BulkCreateResult<T> {
+    /// The created documents, in the order of the given records
+    pub created: Vec<DatabaseRecord<T>>,
+    /// The records which could not be created, as their index in the given records and the matching error
+    pub failed: Vec<(usize, Error)>,
+    /// The created records whose `after_create` hook failed, as their index in the given records and
+    /// the hook error. They are also in `created`, as they are stored in the database.
+    pub hook_failed: Vec<(usize, Error)>,
+}
+
+impl<T> BulkCreateResult<T> {
+    /// Returns `true` if every record was created and passed its hooks
+    #[inline]
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty() && self.hook_failed.is_empty()
+    }
+}
@@ -5,10 +5,16 @@ use std::fmt::{self, Display, Formatter};
 
 use crate::db::database_service;
 use crate::db::database_service::{query_records, query_records_in_batches, raw_query_records};
-use crate::query::{Query, QueryCursor, QueryResult};
-use crate::{DatabaseAccess, EdgeRecord, Error, OperationOptions, Record};
+use crate::query::{Filter, Query, QueryCursor, QueryOptions, QueryResult};
+use crate::{BulkCreateResult, DatabaseAccess, EdgeRecord, Error, OperationOptions, Record};
 use std::ops::{Deref, DerefMut};
 
+/// The document replacing or the patch updating the document matched by an upsert
+enum UpsertUpdate<T> {
+    Replace(T),
+    Patch(Value),
+}
+
 /// Struct representing database stored documents.
 ///
 /// The document of type `T` mut implement [`Record`]
@@ -59,6 +65,61 @@ impl<T: Record> DatabaseRecord<T> {
         Ok(res)
     }
 
+    #[maybe_async::maybe_async]
+    #[allow(clippy::needless_pass_by_value)]
+    async fn __upsert_with_options<D>(
+        search: Filter,
+        mut insert: T,
+        update: UpsertUpdate<T>,
+        db_accessor: &D,
+        options: OperationOptions,
+    ) -> Result<Self, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        let launch_hooks = !options.ignore_hooks;
+        // Only the before hook of the operation expected from the current documents is launched
+        let matching = if launch_hooks {
+            let query = Query::new(T::COLLECTION_NAME)
+                .filter(search.clone())
+                .limit(1, None);
+            !query.raw_call(db_accessor).await?.is_empty()
+        } else {
+            false
+        };
+        if launch_hooks && !matching {
+            insert.before_create_hook(db_accessor).await?;
+        }
+        let (update, replace) = match update {
+            UpsertUpdate::Replace(mut update) => {
+                if launch_hooks && matching {
+                    update.before_save_hook(db_accessor).await?;
+                }
+                (serde_json::to_value(update)?, true)
+            }
+            UpsertUpdate::Patch(patch) => (patch, false),
+        };
+        let result = database_service::upsert_record(
+            search,
+            serde_json::to_value(insert)?,
+            update,
+            replace,
+            db_accessor,
+            T::COLLECTION_NAME,
+            &options,
+        )
+        .await?;
+        let mut res: Self = result.record;
+        if launch_hooks {
+            if result.created {
+                res.record.after_create_hook(db_accessor).await?;
+            } else {
+                res.record.after_save_hook(db_accessor).await?;
+            }
+        }
+        Ok(res)
+    }
+
     /// Creates a document in database.
     /// The function will write a new document and return a database record containing the newly created key
     ///
@@ -171,6 +232,121 @@ impl<T: Record> DatabaseRecord<T> {
         Self::create_with_options(record, db_accessor, db_accessor.operation_options()).await
     }
 
+    /// Creates multiple documents in database.
+    /// The documents are inserted by batches through the bulk document API instead of one request
+    /// per document.
+    ///
+    /// # Note
+    ///
+    /// If you want global operation options (always wait for sync, always ignore hooks, etc)
+    /// configure your [`DatabaseConnection`] with `with_operation_options` to have a customs set
+    /// of default options.
+    ///
+    /// # Hooks
+    ///
+    /// This function will launch `T` hooks `before_create` and `after_create` for every record
+    /// unless the `options` argument disables hooks.
+    /// A record failing its `before_create` hook (a validation for example) is not created, a record
+    /// failing its `after_create` hook is created and its error is listed in `hook_failed`.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - The documents to create
+    /// * `db_accessor` - database connection reference
+    /// * `options` - Operation options to apply
+    ///
+    /// # Returns
+    ///
+    /// On success a [`BulkCreateResult`] is returned, containing the created documents, the errors
+    /// of every record that failed its `before_create` hook or was rejected by the database and the
+    /// `after_create` hook errors of created records, along with the record index.
+    /// An [`Error`] is returned if a batch query failed.
+    ///
+    /// [`Error`]: crate::Error
+    /// [`BulkCreateResult`]: crate::BulkCreateResult
+    /// [`DatabaseConnection`]: crate::DatabaseConnection
+    #[maybe_async::maybe_async]
+    #[allow(clippy::needless_pass_by_value)]
+    pub async fn create_many_with_options<D>(
+        records: Vec<T>,
+        db_accessor: &D,
+        options: OperationOptions,
+    ) -> Result<BulkCreateResult<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        let launch_hooks = !options.ignore_hooks;
+        let mut failed = Vec::new();
+        let mut documents = Vec::with_capacity(records.len());
+        for (index, mut record) in records.into_iter().enumerate() {
+            if launch_hooks {
+                if let Err(error) = record.before_create_hook(db_accessor).await {
+                    failed.push((index, error));
+                    continue;
+                }
+            }
+            documents.push((index, record));
+        }
+        let results =
+            database_service::create_records(documents, db_accessor, T::COLLECTION_NAME, &options)
+                .await?;
+        let mut created = Vec::with_capacity(results.len());
+        let mut hook_failed = Vec::new();
+        for (index, result) in results {
+            match result {
+                Ok(mut res) => {
+                    if launch_hooks {
+                        if let Err(error) = res.record.after_create_hook(db_accessor).await {
+                            hook_failed.push((index, error));
+                        }
+                    }
+                    created.push(res);
+                }
+                Err(error) => failed.push((index, error)),
+            }
+        }
+        failed.sort_by_key(|(index, _)| *index);
+        Ok(BulkCreateResult {
+            created,
+            failed,
+            hook_failed,
+        })
+    }
+
+    /// Creates multiple documents in database.
+    /// The documents are inserted by batches through the bulk document API instead of one request
+    /// per document.
+    ///
+    /// # Hooks
+    ///
+    /// This function will launch `T` hooks `before_create` and `after_create` for every record
+    /// unless the `db_accessor` operations options specifically disable hooks.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - The documents to create
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Returns
+    ///
+    /// On success a [`BulkCreateResult`] is returned, containing the created documents, the errors
+    /// of every record that failed its `before_create` hook or was rejected by the database and the
+    /// `after_create` hook errors of created records, along with the record index.
+    /// An [`Error`] is returned if a batch query failed.
+    ///
+    /// [`Error`]: crate::Error
+    /// [`BulkCreateResult`]: crate::BulkCreateResult
+    #[maybe_async::maybe_async]
+    pub async fn create_many<D>(
+        records: Vec<T>,
+        db_accessor: &D,
+    ) -> Result<BulkCreateResult<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        Self::create_many_with_options(records, db_accessor, db_accessor.operation_options()).await
+    }
+
     /// Creates a document in database with a custom key.
     /// The function will write a new document and return a database record containing the newly created key
     ///
@@ -243,6 +419,235 @@ impl<T: Record> DatabaseRecord<T> {
         .await
     }
 
+    /// Atomically creates or replaces a document in database, according to a `search` filter.
+    /// If no document matches `search`, `insert` is created, otherwise the first matching document is
+    /// replaced by `update`.
+    ///
+    /// # Note
+    ///
+    /// This method should be used for very specific cases, prefer using `upsert` instead.
+    /// The query uses the `UPSERT FILTER` syntax which requires `ArangoDB` 3.12 or later.
+    ///
+    /// # Hooks
+    ///
+    /// This function will look up the documents matching `search` and launch `T` hook `before_create`
+    /// on `insert` if none matches, or `before_save` on `update` otherwise. Then `after_create` or
+    /// `after_save` is launched on the resulting record depending on the executed operation,
+    /// unless the `options` argument disables hooks.
+    /// A document written between the lookup and the upsert can make the executed operation differ
+    /// from the hooked one.
+    ///
+    /// # Arguments
+    ///
+    /// * `search` - The filter matching the document to update, applied on the collection documents
+    /// * `insert` - The document to create if nothing matches `search`
+    /// * `update` - The document replacing the matching document
+    /// * `db_accessor` - database connection reference
+    /// * `options` - Operation options to apply
+    ///
+    /// # Returns
+    ///
+    /// On success the created or replaced record is returned.
+    /// An [`Error`] is returned if the operation or the hooks failed.
+    ///
+    /// [`Error`]: crate::Error
+    #[maybe_async::maybe_async]
+    pub async fn upsert_with_options<D>(
+        search: Filter,
+        insert: T,
+        update: T,
+        db_accessor: &D,
+        options: OperationOptions,
+    ) -> Result<Self, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        Self::__upsert_with_options(
+            search,
+            insert,
+            UpsertUpdate::Replace(update),
+            db_accessor,
+            options,
+        )
+        .await
+    }
+
+    /// Atomically creates or replaces a document in database, according to a `search` filter.
+    /// If no document matches `search`, `insert` is created, otherwise the first matching document is
+    /// replaced by `update`.
+    ///
+    /// # Hooks
+    ///
+    /// This function will look up the documents matching `search` and launch `T` hook `before_create`
+    /// on `insert` if none matches, or `before_save` on `update` otherwise. Then `after_create` or
+    /// `after_save` is launched on the resulting record depending on the executed operation,
+    /// unless the `db_accessor` operations options specifically disable hooks.
+    /// A document written between the lookup and the upsert can make the executed operation differ
+    /// from the hooked one.
+    ///
+    /// # Arguments
+    ///
+    /// * `search` - The filter matching the document to update, applied on the collection documents
+    /// * `insert` - The document to create if nothing matches `search`
+    /// * `update` - The document replacing the matching document
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Returns
+    ///
+    /// On success the created or replaced record is returned.
+    /// An [`Error`] is returned if the operation or the hooks failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use aragog::query::Comparison;
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// pub struct User {
+    ///     pub email: String,
+    ///     pub name: String,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let user = User { email: "robert@surcouf.fr".to_string(), name: "Robert".to_string() };
+    /// let record = DatabaseRecord::upsert(
+    ///     Comparison::field("email").equals_str(&user.email).into(),
+    ///     user.clone(),
+    ///     user,
+    ///     &db_accessor,
+    /// ).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Error`]: crate::Error
+    #[maybe_async::maybe_async]
+    pub async fn upsert<D>(
+        search: Filter,
+        insert: T,
+        update: T,
+        db_accessor: &D,
+    ) -> Result<Self, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        Self::upsert_with_options(
+            search,
+            insert,
+            update,
+            db_accessor,
+            db_accessor.operation_options(),
+        )
+        .await
+    }
+
+    /// Atomically creates or partially updates a document in database, according to a `search` filter.
+    /// If no document matches `search`, `insert` is created, otherwise the first matching document is
+    /// updated with the `patch` fields.
+    ///
+    /// # Note
+    ///
+    /// This method should be used for very specific cases, prefer using `upsert_patch` instead.
+    /// The query uses the `UPSERT FILTER` syntax which requires `ArangoDB` 3.12 or later.
+    ///
+    /// # Hooks
+    ///
+    /// This function will look up the documents matching `search` and launch `T` hook `before_create`
+    /// on `insert` if none matches. Then `after_create` or `after_save` is launched on the resulting
+    /// record depending on the executed operation, unless the `options` argument
+    /// disables hooks.
+    /// A document written between the lookup and the upsert can make the executed operation differ
+    /// from the hooked one.
+    /// The `before_save` hook can't be launched as `patch` is not a `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `search` - The filter matching the document to update, applied on the collection documents
+    /// * `insert` - The document to create if nothing matches `search`
+    /// * `patch` - The fields to update in the matching document
+    /// * `db_accessor` - database connection reference
+    /// * `options` - Operation options to apply
+    ///
+    /// # Returns
+    ///
+    /// On success the created or updated record is returned.
+    /// An [`Error`] is returned if the operation or the hooks failed.
+    ///
+    /// [`Error`]: crate::Error
+    #[maybe_async::maybe_async]
+    pub async fn upsert_patch_with_options<D, P>(
+        search: Filter,
+        insert: T,
+        patch: &P,
+        db_accessor: &D,
+        options: OperationOptions,
+    ) -> Result<Self, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        P: Serialize + ?Sized + Sync,
+    {
+        let patch = serde_json::to_value(patch)?;
+        Self::__upsert_with_options(
+            search,
+            insert,
+            UpsertUpdate::Patch(patch),
+            db_accessor,
+            options,
+        )
+        .await
+    }
+
+    /// Atomically creates or partially updates a document in database, according to a `search` filter.
+    /// If no document matches `search`, `insert` is created, otherwise the first matching document is
+    /// updated with the `patch` fields.
+    ///
+    /// # Hooks
+    ///
+    /// This function will look up the documents matching `search` and launch `T` hook `before_create`
+    /// on `insert` if none matches. Then `after_create` or `after_save` is launched on the resulting
+    /// record depending on the executed operation, unless the `db_accessor` operations
+    /// options specifically disable hooks.
+    /// A document written between the lookup and the upsert can make the executed operation differ
+    /// from the hooked one.
+    /// The `before_save` hook can't be launched as `patch` is not a `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `search` - The filter matching the document to update, applied on the collection documents
+    /// * `insert` - The document to create if nothing matches `search`
+    /// * `patch` - The fields to update in the matching document
+    /// * `db_accessor` - database connection reference
+    ///
+    /// # Returns
+    ///
+    /// On success the created or updated record is returned.
+    /// An [`Error`] is returned if the operation or the hooks failed.
+    ///
+    /// [`Error`]: crate::Error
+    #[maybe_async::maybe_async]
+    pub async fn upsert_patch<D, P>(
+        search: Filter,
+        insert: T,
+        patch: &P,
+        db_accessor: &D,
+    ) -> Result<Self, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        P: Serialize + ?Sized + Sync,
+    {
+        Self::upsert_patch_with_options(
+            search,
+            insert,
+            patch,
+            db_accessor,
+            db_accessor.operation_options(),
+        )
+        .await
+    }
+
     /// Writes in the database the new state of the record, "saving it".
     ///
     /// # Note
@@ -664,7 +1069,56 @@ impl<T: Record> DatabaseRecord<T> {
     where
         D: DatabaseAccess + ?Sized,
     {
-        query_records(db_accessor, query).await
+        query_records(db_accessor, query, query.query_options()).await
+    }
+
+    /// Retrieves all records from the database matching the associated conditions, executing the
+    /// query with `options` instead of the options set on the `query`.
+    ///
+    /// # Arguments:
+    ///
+    /// * `query` - The `Query` to match
+    /// * `db_accessor` - database connection reference
+    /// * `options` - The execution options of this call
+    ///
+    /// # Returns
+    ///
+    /// See [`get`]
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::query::{Query, QueryOptions};
+    /// # use aragog::{DatabaseConnection, DatabaseRecord, Record};
+    /// # use serde::{Serialize, Deserialize};
+    /// # use std::time::Duration;
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {
+    /// #    username: String,
+    /// # }
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let query = User::query();
+    /// // The same query, with a stricter runtime limit for this call
+    /// let options = QueryOptions::new().max_runtime(Duration::from_secs(1));
+    /// DatabaseRecord::<User>::get_with_options(&query, &db_accessor, options).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`get`]: Self::get
+    #[maybe_async::maybe_async]
+    pub async fn get_with_options<D>(
+        query: &Query,
+        db_accessor: &D,
+        options: QueryOptions,
+    ) -> Result<QueryResult<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        query_records(db_accessor, query, Some(&options)).await
     }
 
     /// Retrieves all records from the database matching the associated conditions in batches.
@@ -724,7 +1178,35 @@ impl<T: Record> DatabaseRecord<T> {
     where
         D: DatabaseAccess + ?Sized,
     {
-        query_records_in_batches(db_accessor, query, batch_size).await
+        query_records_in_batches(db_accessor, query, batch_size, query.query_options()).await
+    }
+
+    /// Retrieves all records from the database matching the associated conditions in batches,
+    /// executing the query with `options` instead of the options set on the `query`.
+    ///
+    /// # Arguments:
+    ///
+    /// * `query` - The `Query` to match
+    /// * `db_accessor` - database connection reference
+    /// * `batch_size`- The maximum number of documents in a batch
+    /// * `options` - The execution options of this call
+    ///
+    /// # Returns
+    ///
+    /// See [`get_in_batches`]
+    ///
+    /// [`get_in_batches`]: Self::get_in_batches
+    #[maybe_async::maybe_async]
+    pub async fn get_in_batches_with_options<D>(
+        query: &Query,
+        db_accessor: &D,
+        batch_size: u32,
+        options: QueryOptions,
+    ) -> Result<QueryCursor<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        query_records_in_batches(db_accessor, query, batch_size, Some(&options)).await
     }
 
     /// Retrieves all records from the database matching the associated conditions.
@@ -939,7 +1421,10 @@ impl<T: Record> DatabaseRecord<T> {
         D: DatabaseAccess + ?Sized,
     {
         let aql = query.aql_str();
-        let aql_query = AqlQuery::new(&aql).batch_size(1).count(true);
+        let mut aql_query = AqlQuery::new(&aql).batch_size(1).count(true);
+        for (var, val) in &query.bind_vars {
+            aql_query = aql_query.bind_var(var, val.clone());
+        }
         match db_accessor
             .database()
             .aql_query_batch::<Value>(aql_query)
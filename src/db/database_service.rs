@@ -1,10 +1,27 @@
 use crate::db::database_record_dto::DatabaseRecordDto;
 use crate::error::ArangoHttpError;
-use crate::query::{Query, QueryCursor, QueryResult};
+use crate::query::utils::generate_bind_var_name;
+use crate::query::{
+    Filter, Query, QueryCursor, QueryOptions, QueryPlan, QueryProfile, QueryResult, QueryStats,
+    QueryWarning,
+};
 use crate::{DatabaseAccess, DatabaseRecord, Error, OperationOptions, Record};
-use arangors_lite::{AqlOptions, AqlQuery};
+use arangors_lite::{AqlQuery, ArangoError as DriverError, ClientError, Cursor};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::convert::TryInto;
 
+#[derive(Deserialize)]
+pub struct UpsertResult<T> {
+    pub record: DatabaseRecord<T>,
+    pub created: bool,
+}
+
+/// Maximum amount of documents inserted by a single request in [`create_records`]
+const CREATE_BATCH_SIZE: usize = 1000;
+
 #[maybe_async::maybe_async]
 pub async fn update_record<T, D>(
     obj: DatabaseRecord<T>,
@@ -48,6 +65,134 @@ where
     response.try_into()
 }
 
+/// The created records or their creation errors, with the index of their input document
+type IndexedRecords<T> = Vec<(usize, Result<DatabaseRecord<T>, Error>)>;
+
+#[maybe_async::maybe_async]
+pub async fn create_records<T, D>(
+    records: Vec<(usize, T)>,
+    db_accessor: &D,
+    collection_name: &str,
+    options: &OperationOptions,
+) -> Result<IndexedRecords<T>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let mut api = format!("_api/document/{}?returnNew=true", collection_name);
+    if let Some(value) = options.wait_for_sync {
+        api = format!("{}&waitForSync={}", api, value);
+    }
+    let mut results = Vec::with_capacity(records.len());
+    let mut records = records.into_iter().peekable();
+    while records.peek().is_some() {
+        let batch: Vec<(usize, T)> = records.by_ref().take(CREATE_BATCH_SIZE).collect();
+        let mut documents = Vec::with_capacity(batch.len());
+        for (_, record) in &batch {
+            documents.push(serde_json::to_value(record)?);
+        }
+        log::debug!(
+            "Creating {} new {} documents through {}",
+            batch.len(),
+            collection_name,
+            api
+        );
+        // The response has the created document or the error of every sent document, in order
+        let responses: Vec<Value> = post_api(db_accessor, &api, &Value::Array(documents)).await?;
+        if responses.len() != batch.len() {
+            return Err(Error::InternalError {
+                message: Some(format!(
+                    "Expected `ArangoDB` to return {} {} documents, got {}",
+                    batch.len(),
+                    collection_name,
+                    responses.len()
+                )),
+            });
+        }
+        for ((index, _), mut response) in batch.into_iter().zip(responses) {
+            let result = if response["error"].as_bool().unwrap_or_default() {
+                // The document errors have no HTTP code
+                let error_num = response["errorNum"].as_u64().unwrap_or_default();
+                response["code"] = json!(bulk_error_code(error_num));
+                let error: DriverError = serde_json::from_value(response)?;
+                Err(Error::from(ClientError::Arango(error)))
+            } else {
+                serde_json::from_value(response["new"].take()).map_err(Error::from)
+            };
+            results.push((index, result));
+        }
+    }
+    Ok(results)
+}
+
+/// HTTP code matching the `error_num` of a document rejected by a bulk document request
+const fn bulk_error_code(error_num: u64) -> u16 {
+    match error_num {
+        // Conflict and unique constraint violation
+        1200 | 1210 => 409,
+        // Document or collection not found
+        1202 | 1203 => 404,
+        _ => 400,
+    }
+}
+
+#[maybe_async::maybe_async]
+pub async fn upsert_record<T, D>(
+    mut search: Filter,
+    insert: Value,
+    update: Value,
+    replace: bool,
+    db_accessor: &D,
+    collection_name: &str,
+    options: &OperationOptions,
+) -> Result<UpsertResult<T>, Error>
+where
+    T: Record,
+    D: DatabaseAccess + ?Sized,
+{
+    let mut bind_vars = HashMap::new();
+    search.bind_values(&mut bind_vars);
+    let insert_var = generate_bind_var_name(&bind_vars);
+    bind_vars.insert(insert_var.clone(), insert);
+    let update_var = generate_bind_var_name(&bind_vars);
+    bind_vars.insert(update_var.clone(), update);
+    let mut aql_options = vec![format!("ignoreRevs: {}", options.ignore_revs)];
+    if !replace {
+        aql_options.push(String::from("keepNull: true"));
+    }
+    if let Some(value) = options.wait_for_sync {
+        aql_options.push(format!("waitForSync: {}", value));
+    }
+    let aql = format!(
+        "UPSERT FILTER {} INSERT @{} {} @{} IN {} OPTIONS {{ {} }} RETURN {{ record: NEW, created: IS_NULL(OLD) }}",
+        search.aql_str("CURRENT"),
+        insert_var,
+        if replace { "REPLACE" } else { "UPDATE" },
+        update_var,
+        collection_name,
+        aql_options.join(", ")
+    );
+    log::debug!(
+        "Upserting {} document through AQL: `{}`",
+        collection_name,
+        aql
+    );
+    let mut aql_query = AqlQuery::new(&aql);
+    for (var, val) in &bind_vars {
+        aql_query = aql_query.bind_var(var, val.clone());
+    }
+    let result: Vec<UpsertResult<T>> = match db_accessor.database().aql_query(aql_query).await {
+        Ok(value) => value,
+        Err(error) => return Err(Error::from(error)),
+    };
+    result.into_iter().next().ok_or(Error::InternalError {
+        message: Some(format!(
+            "Expected `ArangoDB` to return the upserted {} document",
+            collection_name
+        )),
+    })
+}
+
 #[maybe_async::maybe_async]
 pub async fn retrieve_record<T, D>(
     key: &str,
@@ -121,38 +266,112 @@ where
 }
 
 #[maybe_async::maybe_async]
-pub async fn query_records<T, D>(db_accessor: &D, query: &Query) -> Result<QueryResult<T>, Error>
+pub async fn query_records<T, D>(
+    db_accessor: &D,
+    query: &Query,
+    options: Option<&QueryOptions>,
+) -> Result<QueryResult<T>, Error>
 where
     T: Record,
     D: DatabaseAccess + ?Sized,
 {
+    query.validate()?;
     let aql = query.aql_str();
     log::debug!(
         "Querying {} records through AQL: `{}`",
         T::COLLECTION_NAME,
         aql
     );
-    let mut aql_query = AqlQuery::new(&aql);
-    for (var, val) in &query.bind_vars {
-        aql_query = aql_query.bind_var(var, val.clone());
-    }
-    let query_result = match db_accessor.database().aql_query(aql_query).await {
-        Ok(value) => value,
-        Err(error) => return Err(Error::from(error)),
-    };
+    let body = cursor_body(&aql, &query.bind_vars, options, false);
+    let query_result: Vec<DatabaseRecord<T>> = query_all(db_accessor, &body).await?;
     Ok(query_result.into())
 }
 
+#[maybe_async::maybe_async]
+pub async fn query_values<T, D>(db_accessor: &D, query: &Query) -> Result<Vec<T>, Error>
+where
+    T: DeserializeOwned,
+    D: DatabaseAccess + ?Sized,
+{
+    query.validate()?;
+    let aql = query.aql_str();
+    log::debug!("Querying values through AQL: `{}`", aql);
+    let body = cursor_body(&aql, &query.bind_vars, query.query_options(), false);
+    query_all(db_accessor, &body).await
+}
+
+#[maybe_async::maybe_async]
+pub async fn count_records<D>(db_accessor: &D, query: &Query) -> Result<usize, Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    query.validate()?;
+    let aql = query.count_aql_str();
+    log::debug!("Counting records through AQL: `{}`", aql);
+    let body = cursor_body(&aql, &query.bind_vars, query.query_options(), false);
+    let values: Vec<usize> = query_all(db_accessor, &body).await?;
+    Ok(values.into_iter().next().unwrap_or_default())
+}
+
+#[maybe_async::maybe_async]
+pub async fn query_aql_values<T, D>(
+    db_accessor: &D,
+    aql: &str,
+    bind_vars: &HashMap<String, Value>,
+    options: Option<&QueryOptions>,
+) -> Result<Vec<T>, Error>
+where
+    T: DeserializeOwned,
+    D: DatabaseAccess + ?Sized,
+{
+    log::debug!("Querying values through AQL: `{}`", aql);
+    let body = cursor_body(aql, bind_vars, options, false);
+    query_all(db_accessor, &body).await
+}
+
+/// Runs the `query` in a single batch, with its `fullCount` statistic if `full_count` is set
+#[maybe_async::maybe_async]
+pub async fn query_values_with_full_count<T, D>(
+    db_accessor: &D,
+    query: &Query,
+    batch_size: u32,
+    full_count: bool,
+) -> Result<(Vec<T>, Option<usize>), Error>
+where
+    T: DeserializeOwned,
+    D: DatabaseAccess + ?Sized,
+{
+    query.validate()?;
+    let aql = query.aql_str();
+    log::debug!("Querying values through AQL: `{}`", aql);
+    let options = query
+        .query_options()
+        .cloned()
+        .unwrap_or_default()
+        .full_count(full_count);
+    let mut body = cursor_body(&aql, &query.bind_vars, Some(&options), false);
+    body["batchSize"] = json!(batch_size);
+    let cursor: Cursor<T> = post_api(db_accessor, "_api/cursor", &body).await?;
+    let full_count = cursor
+        .extra
+        .as_ref()
+        .and_then(|extra| extra.stats.as_ref())
+        .and_then(|stats| stats.full_count);
+    Ok((cursor.result, full_count))
+}
+
 #[maybe_async::maybe_async]
 pub async fn query_records_in_batches<T, D>(
     db_accessor: &D,
     query: &Query,
     batch_size: u32,
+    options: Option<&QueryOptions>,
 ) -> Result<QueryCursor<T>, Error>
 where
     T: Record,
     D: DatabaseAccess + ?Sized,
 {
+    query.validate()?;
     let aql = query.aql_str();
     log::debug!(
         "Querying {} records through AQL with {} batch size: `{}`",
@@ -160,15 +379,237 @@ where
         batch_size,
         aql
     );
-    let mut aql_query = AqlQuery::new(&aql)
-        .batch_size(batch_size)
-        .options(AqlOptions::builder().full_count(true).build());
-    for (var, val) in &query.bind_vars {
-        aql_query = aql_query.bind_var(var, val.clone());
-    }
-    let cursor = match db_accessor.database().aql_query_batch(aql_query).await {
-        Ok(value) => value,
-        Err(error) => return Err(Error::from(error)),
-    };
+    let mut body = cursor_body(&aql, &query.bind_vars, options, true);
+    body["batchSize"] = json!(batch_size);
+    let cursor: Cursor<DatabaseRecord<T>> = post_api(db_accessor, "_api/cursor", &body).await?;
     Ok(QueryCursor::new(cursor, db_accessor.database().clone()))
 }
+
+#[derive(Deserialize)]
+struct ExplainResponse {
+    plan: QueryPlan,
+    #[serde(default)]
+    warnings: Vec<QueryWarning>,
+}
+
+#[derive(Deserialize)]
+struct ProfileResponse {
+    id: Option<String>,
+    extra: ProfileExtra,
+}
+
+#[derive(Deserialize)]
+struct ProfileExtra {
+    plan: QueryPlan,
+    #[serde(default)]
+    warnings: Vec<QueryWarning>,
+    stats: QueryStats,
+    #[serde(default)]
+    profile: HashMap<String, f64>,
+}
+
+/// Retrieves the execution plan of the `query` through the explain API, without running it
+#[maybe_async::maybe_async]
+pub async fn explain_query<D>(db_accessor: &D, query: &Query) -> Result<QueryPlan, Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    query.validate()?;
+    let aql = query.aql_str();
+    log::debug!("Explaining AQL: `{}`", aql);
+    let body = query_api_body(&aql, &query.bind_vars, query.query_options(), json!({}));
+    let response: ExplainResponse = post_api(db_accessor, "_api/explain", &body).await?;
+    let mut plan = response.plan;
+    plan.warnings = response.warnings;
+    Ok(plan)
+}
+
+/// Runs the `query` with profiling and retrieves its executed plan and statistics.
+/// The query results are dropped.
+#[maybe_async::maybe_async]
+pub async fn profile_query<D>(db_accessor: &D, query: &Query) -> Result<QueryProfile, Error>
+where
+    D: DatabaseAccess + ?Sized,
+{
+    query.validate()?;
+    let aql = query.aql_str();
+    log::debug!("Profiling AQL: `{}`", aql);
+    let mut body = query_api_body(
+        &aql,
+        &query.bind_vars,
+        query.query_options(),
+        json!({ "profile": 2 }),
+    );
+    // Only the first result is transferred, the statistics cover the whole execution
+    body["batchSize"] = json!(1);
+    let response: ProfileResponse = post_api(db_accessor, "_api/cursor", &body).await?;
+    if let Some(id) = response.id {
+        // The remaining batches are not needed
+        let database = db_accessor.database();
+        if let Ok(url) = database.url().join(&format!("_api/cursor/{}", id)) {
+            if let Err(error) = database.session().delete(url.to_string(), "").await {
+                log::warn!("Failed to delete the AQL cursor {}: {}", id, error);
+            }
+        }
+    }
+    let mut plan = response.extra.plan;
+    plan.warnings = response.extra.warnings;
+    Ok(QueryProfile {
+        plan,
+        stats: response.extra.stats,
+        phases: response.extra.profile,
+    })
+}
+
+/// Renders the body of a query API request for `aql` with its bind parameters and the given
+/// `options`, extended with the `api_options`. The unset options keep the server defaults.
+fn query_api_body(
+    aql: &str,
+    bind_vars: &HashMap<String, Value>,
+    options: Option<&QueryOptions>,
+    mut api_options: Value,
+) -> Value {
+    let mut body = json!({
+        "query": aql,
+        "bindVars": bind_vars,
+    });
+    if let Some(options) = options {
+        if let Some(memory_limit) = options.memory_limit {
+            body["memoryLimit"] = json!(memory_limit);
+        }
+        if let Some(max_runtime) = options.max_runtime {
+            api_options["maxRuntime"] = json!(max_runtime.as_secs_f64());
+        }
+        if let Some(fail_on_warning) = options.fail_on_warning {
+            api_options["failOnWarning"] = json!(fail_on_warning);
+        }
+        if !options.optimizer_rules.is_empty() {
+            api_options["optimizer"] = json!({ "rules": options.optimizer_rules });
+        }
+    }
+    body["options"] = api_options;
+    body
+}
+
+/// Renders the `_api/cursor` request body of `aql` with its bind parameters and the given
+/// `options`. The `fullCount` statistic is requested if `full_count` is set, unless the options
+/// say otherwise.
+fn cursor_body(
+    aql: &str,
+    bind_vars: &HashMap<String, Value>,
+    options: Option<&QueryOptions>,
+    full_count: bool,
+) -> Value {
+    let mut api_options = json!({});
+    match options.and_then(|options| options.full_count) {
+        Some(full_count) => api_options["fullCount"] = json!(full_count),
+        None if full_count => api_options["fullCount"] = json!(true),
+        None => (),
+    }
+    if let Some(stream) = options.and_then(|options| options.stream) {
+        api_options["stream"] = json!(stream);
+    }
+    let mut body = query_api_body(aql, bind_vars, options, api_options);
+    if let Some(options) = options {
+        if let Some(cache) = options.cache {
+            body["cache"] = json!(cache);
+        }
+        if let Some(ttl) = options.ttl {
+            body["ttl"] = json!(ttl);
+        }
+    }
+    body
+}
+
+/// Runs the cursor request `body` and retrieves every batch of the results
+#[maybe_async::maybe_async]
+async fn query_all<D, T>(db_accessor: &D, body: &Value) -> Result<Vec<T>, Error>
+where
+    D: DatabaseAccess + ?Sized,
+    T: DeserializeOwned,
+{
+    let mut cursor: Cursor<T> = post_api(db_accessor, "_api/cursor", body).await?;
+    let mut results = Vec::new();
+    loop {
+        results.append(&mut cursor.result);
+        let id = match cursor.id {
+            Some(id) if cursor.more => id,
+            _ => break,
+        };
+        cursor = db_accessor
+            .database()
+            .aql_next_batch(&id)
+            .await
+            .map_err(Error::from)?;
+    }
+    Ok(results)
+}
+
+/// Sends `body` to the `api` endpoint of the database and deserializes the response
+#[maybe_async::maybe_async]
+async fn post_api<D, T>(db_accessor: &D, api: &str, body: &Value) -> Result<T, Error>
+where
+    D: DatabaseAccess + ?Sized,
+    T: DeserializeOwned,
+{
+    let database = db_accessor.database();
+    let url = database
+        .url()
+        .join(api)
+        .map_err(|error| Error::InternalError {
+            message: Some(error.to_string()),
+        })?;
+    let response = database
+        .session()
+        .post(url.to_string(), body.to_string())
+        .await
+        .map_err(Error::from)?;
+    let value: Value = serde_json::from_str(response.body())?;
+    if value["error"].as_bool().unwrap_or_default() {
+        let error: DriverError = serde_json::from_value(value)?;
+        return Err(Error::from(ClientError::Arango(error)));
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn unset_options_are_not_sent() {
+        let body = cursor_body("RETURN 1", &HashMap::new(), None, false);
+        assert_eq!(body["options"], json!({}));
+        let body = cursor_body(
+            "RETURN 1",
+            &HashMap::new(),
+            Some(&QueryOptions::new().cache(false)),
+            true,
+        );
+        assert_eq!(body["options"], json!({ "fullCount": true }));
+        assert_eq!(body["cache"], json!(false));
+        assert!(body.get("ttl").is_none());
+    }
+
+    #[test]
+    fn options_are_sent() {
+        let options = QueryOptions::new()
+            .max_runtime(Duration::from_millis(1500))
+            .stream(true)
+            .full_count(false)
+            .fail_on_warning(true)
+            .optimizer_rules(&["-all"]);
+        let body = cursor_body("RETURN 1", &HashMap::new(), Some(&options), true);
+        assert_eq!(
+            body["options"],
+            json!({
+                "fullCount": false,
+                "stream": true,
+                "maxRuntime": 1.5,
+                "failOnWarning": true,
+                "optimizer": { "rules": ["-all"] }
+            })
+        );
+    }
+}
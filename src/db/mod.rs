@@ -1,10 +1,12 @@
+pub mod bulk_create_result;
 pub mod database_access;
 pub mod database_collection;
 pub mod database_connection;
 pub mod database_connection_builder;
 pub mod database_record;
 mod database_record_dto;
-mod database_service;
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) mod database_service;
 pub mod operation_options;
 /// The transaction module
 pub mod transaction;
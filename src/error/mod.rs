@@ -39,8 +39,11 @@ pub enum Error {
     ///
     /// Can be interpreted as a HTTP code `422` Unprocessable Entity.
     UnprocessableEntity {
-        /// The source error
-        source: Box<dyn std::error::Error>,
+        /// The source error, `Send + Sync` so that errors can be kept across `.await` points by
+        /// the `Send` futures of the [`Record`] methods, like the `create_many` failures
+        ///
+        /// [`Record`]: crate::Record
+        source: Box<dyn std::error::Error + Send + Sync>,
     },
     /// The ArangoDb Error as returned by the database host
     ///
@@ -500,10 +500,10 @@ pub use aragog_macros::*;
 #[cfg(not(feature = "minimal_traits"))]
 pub use {authorize_action::AuthorizeAction, new::New, update::Update};
 pub use {
-    db::database_access::DatabaseAccess, db::database_connection::AuthMode,
-    db::database_connection::DatabaseConnection, db::database_record::DatabaseRecord,
-    db::operation_options::OperationOptions, db::transaction, edge_record::EdgeRecord,
-    error::Error, foreign_link::ForeignLink, link::Link, record::Record,
+    db::bulk_create_result::BulkCreateResult, db::database_access::DatabaseAccess,
+    db::database_connection::AuthMode, db::database_connection::DatabaseConnection,
+    db::database_record::DatabaseRecord, db::operation_options::OperationOptions, db::transaction,
+    edge_record::EdgeRecord, error::Error, foreign_link::ForeignLink, link::Link, record::Record,
     undefined_record::UndefinedRecord, validate::Validate,
 };
 
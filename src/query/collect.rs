@@ -0,0 +1,265 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// Aggregation functions available in a [`Collect`] `AGGREGATE` clause
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum AggregateFunction {
+    /// Number of aggregated values (`LENGTH`)
+    Length,
+    /// Minimum value (`MIN`)
+    Min,
+    /// Maximum value (`MAX`)
+    Max,
+    /// Sum of the values (`SUM`)
+    Sum,
+    /// Average of the values (`AVERAGE`)
+    Average,
+    /// Array of the distinct values (`UNIQUE`)
+    Unique,
+    /// Number of distinct values (`COUNT_DISTINCT`)
+    CountDistinct,
+}
+
+impl Display for AggregateFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Length => "LENGTH",
+                Self::Min => "MIN",
+                Self::Max => "MAX",
+                Self::Sum => "SUM",
+                Self::Average => "AVERAGE",
+                Self::Unique => "UNIQUE",
+                Self::CountDistinct => "COUNT_DISTINCT",
+            }
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Aggregate {
+    variable: String,
+    function: AggregateFunction,
+    field: String,
+}
+
+/// Grouping and aggregation operation of a [`Query`], rendered as an AQL `COLLECT` statement.
+///
+/// Every declared variable (group, aggregate, count or group content) becomes an attribute of the
+/// returned objects, which can be deserialized with [`Query::call_projection`].
+///
+/// # Note
+///
+/// After a `COLLECT` the queried documents are no longer available, only the declared variables are.
+/// Following [`Query::sort`] calls will sort on these variables, and following filters should use
+/// [`Comparison::statement`] to reference them.
+///
+/// `ArangoDB` doesn't allow to combine [`count_into`] with [`aggregate`] or [`into_group`], such
+/// queries are refused when called.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::query::{AggregateFunction, Collect, Query};
+/// let query = Query::new("Orders").collect(
+///     Collect::new()
+///         .group_by("city", "address.city")
+///         .aggregate("total", AggregateFunction::Sum, "price")
+/// );
+/// assert_eq!(
+///     query.aql_str(),
+///     r#"FOR a in Orders COLLECT city = a.address.city AGGREGATE total = SUM(a.price) return { "city": city, "total": total }"#
+/// );
+/// ```
+///
+/// [`Query`]: crate::query::Query
+/// [`Query::call_projection`]: crate::query::Query::call_projection
+/// [`Query::sort`]: crate::query::Query::sort
+/// [`Comparison::statement`]: crate::query::Comparison::statement
+/// [`count_into`]: Self::count_into
+/// [`aggregate`]: Self::aggregate
+/// [`into_group`]: Self::into_group
+#[derive(Clone, Debug, Default)]
+pub struct Collect {
+    groups: Vec<(String, String)>,
+    aggregates: Vec<Aggregate>,
+    count_variable: Option<String>,
+    group_variable: Option<String>,
+}
+
+impl Collect {
+    /// Instantiates a new empty `Collect`, which will group all documents together.
+    /// At least one variable must be declared with [`group_by`], [`aggregate`], [`count_into`]
+    /// or [`into_group`], the queries with an empty `Collect` are refused when called.
+    ///
+    /// [`group_by`]: Self::group_by
+    /// [`aggregate`]: Self::aggregate
+    /// [`count_into`]: Self::count_into
+    /// [`into_group`]: Self::into_group
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Groups the documents by the value of `field`, stored in `variable`.
+    /// Every call adds a new grouping criteria.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Collect, Query};
+    /// let query = Query::new("Users").collect(Collect::new().group_by("age", "age"));
+    /// assert_eq!(query.aql_str(), r#"FOR a in Users COLLECT age = a.age return { "age": age }"#);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn group_by(mut self, variable: &str, field: &str) -> Self {
+        self.groups.push((variable.to_string(), field.to_string()));
+        self
+    }
+
+    /// Aggregates the values of `field` in each group with `function`, stored in `variable`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{AggregateFunction, Collect, Query};
+    /// let query = Query::new("Users").collect(
+    ///     Collect::new()
+    ///         .aggregate("youngest", AggregateFunction::Min, "age")
+    ///         .aggregate("oldest", AggregateFunction::Max, "age"),
+    /// );
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"FOR a in Users COLLECT AGGREGATE youngest = MIN(a.age), oldest = MAX(a.age) return { "youngest": youngest, "oldest": oldest }"#
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn aggregate(mut self, variable: &str, function: AggregateFunction, field: &str) -> Self {
+        self.aggregates.push(Aggregate {
+            variable: variable.to_string(),
+            function,
+            field: field.to_string(),
+        });
+        self
+    }
+
+    /// Counts the documents of each group, stored in `variable`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Collect, Query};
+    /// let query = Query::new("Users").collect(Collect::new().group_by("age", "age").count_into("count"));
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"FOR a in Users COLLECT age = a.age WITH COUNT INTO count return { "age": age, "count": count }"#
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn count_into(mut self, variable: &str) -> Self {
+        self.count_variable = Some(variable.to_string());
+        self
+    }
+
+    /// Stores the documents of each group in `variable`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Collect, Query};
+    /// let query = Query::new("Users").collect(Collect::new().group_by("age", "age").into_group("users"));
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"FOR a in Users COLLECT age = a.age INTO users = a return { "age": age, "users": users }"#
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn into_group(mut self, variable: &str) -> Self {
+        self.group_variable = Some(variable.to_string());
+        self
+    }
+
+    /// Checks that the declared variables render a valid `COLLECT` statement
+    ///
+    /// # Errors
+    ///
+    /// Fails if no variable is declared or if a count is combined with aggregates or a group
+    /// variable
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        let has_count = self.count_variable.is_some();
+        if self.groups.is_empty()
+            && self.aggregates.is_empty()
+            && !has_count
+            && self.group_variable.is_none()
+        {
+            return Err(Error::ValidationError(String::from(
+                "A `Collect` must declare at least one group, aggregate, count or group variable",
+            )));
+        }
+        if has_count && (!self.aggregates.is_empty() || self.group_variable.is_some()) {
+            return Err(Error::ValidationError(String::from(
+                "A `Collect` count can't be combined with aggregates or a group variable",
+            )));
+        }
+        Ok(())
+    }
+
+    /// Renders the `COLLECT` statement
+    #[must_use]
+    pub(crate) fn aql_str(&self, collection_id: &str) -> String {
+        let mut res = String::from("COLLECT");
+        if !self.groups.is_empty() {
+            let groups: Vec<String> = self
+                .groups
+                .iter()
+                .map(|(variable, field)| format!("{} = {}.{}", variable, collection_id, field))
+                .collect();
+            res = format!("{} {}", res, groups.join(", "));
+        }
+        if !self.aggregates.is_empty() {
+            let aggregates: Vec<String> = self
+                .aggregates
+                .iter()
+                .map(|aggregate| {
+                    format!(
+                        "{} = {}({}.{})",
+                        aggregate.variable, aggregate.function, collection_id, aggregate.field
+                    )
+                })
+                .collect();
+            res = format!("{} AGGREGATE {}", res, aggregates.join(", "));
+        }
+        if let Some(variable) = &self.group_variable {
+            res = format!("{} INTO {} = {}", res, variable, collection_id);
+        }
+        if let Some(variable) = &self.count_variable {
+            res = format!("{} WITH COUNT INTO {}", res, variable);
+        }
+        res
+    }
+
+    /// Renders the object returning every declared variable
+    #[must_use]
+    pub(crate) fn return_aql_str(&self) -> String {
+        let variables: Vec<String> = self
+            .groups
+            .iter()
+            .map(|(variable, _)| variable)
+            .chain(self.aggregates.iter().map(|aggregate| &aggregate.variable))
+            .chain(self.group_variable.iter())
+            .chain(self.count_variable.iter())
+            .map(|variable| format!(r#""{}": {}"#, variable, variable))
+            .collect();
+        format!("{{ {} }}", variables.join(", "))
+    }
+}
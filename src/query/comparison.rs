@@ -1,9 +1,16 @@
-use std::fmt::Display;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
 
 use num::Num;
+use serde_json::{json, Value};
 
-use crate::query::utils::{string_array_from_array, string_array_from_array_str};
+use crate::query::evaluation::{
+    compare, geo_contains, geo_distance, geo_intersects, resolve, split_quantifier, Quantifier,
+};
+use crate::query::projection::traversal_variables;
+use crate::query::utils::{generate_bind_var_name, geo_point};
 use crate::query::Filter;
+use crate::Error;
 
 /// Macro to simplify the [`Comparison`] construction:
 ///
@@ -54,27 +61,118 @@ macro_rules! compare {
 /// Builder for [`Comparison`]
 #[derive(Clone, Debug)]
 pub struct ComparisonBuilder {
-    is_field: bool,
+    target: ComparisonTarget,
     statement: String,
 }
 
+/// Right value of a [`Comparison`]
+#[derive(Clone, Debug)]
+pub enum ComparisonValue {
+    /// AQL literal rendered as is (`null`, `true`, `false`)
+    Statement(String),
+    /// User value not yet registered as a bind parameter of a [`Query`]
+    ///
+    /// [`Query`]: crate::query::Query
+    Value(Value),
+    /// Name of the bind parameter holding the value in the owning [`Query`]
+    ///
+    /// [`Query`]: crate::query::Query
+    BindVar(String),
+}
+
+/// Left value of a [`Comparison`]
+#[derive(Copy, Clone, Debug)]
+enum ComparisonTarget {
+    /// A field of the current document (`a.field`)
+    Field,
+    /// A field of the traversal path (`a_path.vertices[*].field`)
+    PathField,
+    /// A statement rendered as is
+    Statement,
+}
+
+/// Geo function applied to the left value of a [`Comparison`]
+#[derive(Clone, Debug)]
+enum ComparisonFunction {
+    /// `GEO_DISTANCE(left, point) comparator right`, `point` being a `[longitude, latitude]` pair
+    Distance(ComparisonValue),
+    /// `GEO_CONTAINS(right, left)`
+    Contains,
+    /// `GEO_INTERSECTS(right, left)`
+    Intersects,
+}
+
 /// Struct representing one AQL comparison in a [`Query`].
 ///
+/// # Note
+///
+/// The compared values are never interpolated in the AQL string: when the comparison is added to a
+/// [`Query`], the values are registered in its [`bind_vars`] and the rendered query uses `@var`
+/// references. A standalone `Comparison` renders its value as a JSON literal.
+///
 /// [`Query`]: crate::query::Query
+/// [`bind_vars`]: crate::query::Query::bind_vars
 #[derive(Clone, Debug)]
 pub struct Comparison {
-    is_field: bool,
+    target: ComparisonTarget,
+    function: Option<ComparisonFunction>,
     left_value: String,
     comparator: String,
-    right_value: String,
+    right_value: ComparisonValue,
+}
+
+impl ComparisonValue {
+    /// Registers the user value in `bind_vars` under a generated name, if not already done.
+    pub fn bind(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        if let Self::Value(value) = self {
+            let name = generate_bind_var_name(bind_vars);
+            bind_vars.insert(name.clone(), value.clone());
+            *self = Self::BindVar(name);
+        }
+    }
+
+    /// Takes back the user value from `bind_vars`, reverting [`bind`].
+    ///
+    /// [`bind`]: Self::bind
+    pub fn unbind(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        if let Self::BindVar(name) = self {
+            if let Some(value) = bind_vars.remove(name) {
+                *self = Self::Value(value);
+            }
+        }
+    }
+
+    /// The JSON value of an unbound value or of a literal statement
+    fn evaluated(&self) -> Result<Value, Error> {
+        match self {
+            Self::Value(value) => Ok(value.clone()),
+            Self::Statement(statement) => serde_json::from_str(statement).map_err(|_| {
+                Error::ValidationError(format!("Can't evaluate the `{}` statement", statement))
+            }),
+            Self::BindVar(name) => Err(Error::ValidationError(format!(
+                "Can't evaluate the `@{}` bind parameter of a query",
+                name
+            ))),
+        }
+    }
+}
+
+impl Display for ComparisonValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Statement(statement) => write!(f, "{}", statement),
+            Self::Value(value) => write!(f, "{}", value),
+            Self::BindVar(name) => write!(f, "@{}", name),
+        }
+    }
 }
 
 impl ComparisonBuilder {
     /// Finalizes the current query item builder with a string equality comparison.
     ///
     /// # Note
-    /// The field to be matched should be a string value as the AQL translation will put it between quotes.
-    /// This means that if you use this with a numeric the final result will be between quotes.
+    /// The value is converted to a string before being bound to the query.
+    /// This means that if you use this with a numeric the bound value will be a string.
     ///
     /// # Example
     ///
@@ -84,7 +182,8 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("username").equals_str("felix");
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Users FILTER a.username == "felix" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.username == @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], "felix");
     /// ```
     /// - Numeric example:
     /// ```rust
@@ -93,12 +192,14 @@ impl ComparisonBuilder {
     /// // With the String equality
     /// let query_item = Comparison::field("price").equals_str(10.5);
     /// let query = Query::new("Product").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Product FILTER a.price == "10.5" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Product FILTER a.price == @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], "10.5");
     ///
     /// // With simple equality
     /// let query_item = Comparison::field("price").equals(10.5);
     /// let query = Query::new("Product").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Product FILTER a.price == 10.5 return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Product FILTER a.price == @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], 10.5);
     /// ```
     #[inline]
     #[must_use]
@@ -107,18 +208,19 @@ impl ComparisonBuilder {
         T: Display,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "==".to_string(),
-            right_value: format!(r#""{}""#, value),
+            right_value: ComparisonValue::Value(Value::String(value.to_string())),
         }
     }
 
     /// Finalizes the current query item builder with a string inequality comparison.
     ///
     /// # Note
-    /// The field to be matched should be a string value as the AQL translation will put it between quotes.
-    /// This means that if you use this with a numeric the final result will be between quotes.
+    /// The value is converted to a string before being bound to the query.
+    /// This means that if you use this with a numeric the bound value will be a string.
     ///
     /// # Example
     ///
@@ -128,21 +230,24 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("username").different_than_str("felix");
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Users FILTER a.username != "felix" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.username != @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], "felix");
     /// ```
     /// - Numeric example:
     /// ```rust
     /// # use aragog::query::{Comparison, Query, Filter};
     /// #
-    /// // With the String equality
+    /// // With the String inequality
     /// let query_item = Comparison::field("price").different_than_str(10.5);
     /// let query = Query::new("Product").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Product FILTER a.price != "10.5" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Product FILTER a.price != @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], "10.5");
     ///
-    /// // With simple equality
+    /// // With simple inequality
     /// let query_item = Comparison::field("price").different_than(10.5);
     /// let query = Query::new("Product").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Product FILTER a.price != 10.5 return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Product FILTER a.price != @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], 10.5);
     /// ```
     #[inline]
     #[must_use]
@@ -151,10 +256,11 @@ impl ComparisonBuilder {
         T: Display,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "!=".to_string(),
-            right_value: format!(r#""{}""#, value),
+            right_value: ComparisonValue::Value(Value::String(value.to_string())),
         }
     }
 
@@ -168,16 +274,17 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("username").matches(r#"^[0.9](0.6)$"#);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Users FILTER a.username =~ "^[0.9](0.6)$" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.username =~ @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
     pub fn matches(self, regular_expression: &str) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "=~".to_string(),
-            right_value: format!(r#""{}""#, regular_expression),
+            right_value: ComparisonValue::Value(Value::from(regular_expression)),
         }
     }
 
@@ -191,16 +298,17 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("username").does_not_match(r#"^[0.9](0.6)$"#);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Users FILTER a.username !~ "^[0.9](0.6)$" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.username !~ @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
     pub fn does_not_match(self, regular_expression: &str) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "!~".to_string(),
-            right_value: format!(r#""{}""#, regular_expression),
+            right_value: ComparisonValue::Value(Value::from(regular_expression)),
         }
     }
 
@@ -214,16 +322,17 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("username").like("%felix%");
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Users FILTER a.username LIKE "%felix%" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.username LIKE @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
     pub fn like(self, pattern: &str) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "LIKE".to_string(),
-            right_value: format!(r#""{}""#, pattern),
+            right_value: ComparisonValue::Value(Value::from(pattern)),
         }
     }
 
@@ -237,24 +346,25 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("username").not_like("%felix%");
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Users FILTER a.username NOT LIKE "%felix%" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.username NOT LIKE @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
     pub fn not_like(self, pattern: &str) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "NOT LIKE".to_string(),
-            right_value: format!(r#""{}""#, pattern),
+            right_value: ComparisonValue::Value(Value::from(pattern)),
         }
     }
 
     /// Finalizes the current query item builder with an equality comparison.
     ///
     /// # Note
-    /// The field will not be put between quotes. This means you cannot use this for string comparison
-    /// Use [`equals_str`] instead
+    /// The value keeps its JSON type when bound to the query, so `"18"` and `18` are different.
+    /// Use [`equals_str`] for values that should always be compared as strings.
     ///
     /// # Example
     ///
@@ -264,21 +374,17 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("age").equals(18);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age == 18 return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age == @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], 18);
     /// ```
     /// - String example:
     /// ```rust
     /// # use aragog::query::{Comparison, Query, Filter};
     /// #
-    /// // With simple equality the comparison will fail
     /// let query_item = Comparison::field("username").equals("felix");
     /// let query = Query::new("User").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in User FILTER a.username == felix return a");
-    ///
-    /// // With the String equality it would work
-    /// let query_item = Comparison::field("username").equals_str("felix");
-    /// let query = Query::new("User").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in User FILTER a.username == "felix" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in User FILTER a.username == @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], "felix");
     /// ```
     ///
     /// [`equals_str`]: Self::equals_str
@@ -286,21 +392,22 @@ impl ComparisonBuilder {
     #[must_use]
     pub fn equals<T>(self, value: T) -> Comparison
     where
-        T: Display,
+        T: Into<Value>,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "==".to_string(),
-            right_value: format!(r#"{}"#, value),
+            right_value: ComparisonValue::Value(value.into()),
         }
     }
 
-    /// Finalizes the current query item builder with an ineequality comparison.
+    /// Finalizes the current query item builder with an inequality comparison.
     ///
     /// # Note
-    /// The field will not be put between quotes. This means you cannot use this for string comparison
-    /// Use [`different_than_str`] instead
+    /// The value keeps its JSON type when bound to the query, so `"18"` and `18` are different.
+    /// Use [`different_than_str`] for values that should always be compared as strings.
     ///
     /// # Example
     ///
@@ -310,21 +417,17 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("age").different_than(18);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age != 18 return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age != @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], 18);
     /// ```
     /// - String example:
     /// ```rust
     /// # use aragog::query::{Comparison, Query, Filter};
     /// #
-    /// // With simple inequality the comparison will fail
     /// let query_item = Comparison::field("username").different_than("felix");
     /// let query = Query::new("User").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in User FILTER a.username != felix return a");
-    ///
-    /// // With the String inequality it would work
-    /// let query_item = Comparison::field("username").different_than_str("felix");
-    /// let query = Query::new("User").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in User FILTER a.username != "felix" return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in User FILTER a.username != @aragog_0 return a");
+    /// assert_eq!(query.bind_vars["aragog_0"], "felix");
     /// ```
     ///
     /// [`different_than_str`]: Self::different_than_str
@@ -332,13 +435,14 @@ impl ComparisonBuilder {
     #[must_use]
     pub fn different_than<T>(self, value: T) -> Comparison
     where
-        T: Display,
+        T: Into<Value>,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "!=".to_string(),
-            right_value: format!(r#"{}"#, value),
+            right_value: ComparisonValue::Value(value.into()),
         }
     }
 
@@ -352,20 +456,20 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("age").greater_than(18);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age > 18 return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age > @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
-    #[allow(clippy::needless_pass_by_value)]
     pub fn greater_than<T>(self, value: T) -> Comparison
     where
-        T: Num + Display,
+        T: Num + Into<Value>,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: ">".to_string(),
-            right_value: format!(r#"{}"#, value),
+            right_value: ComparisonValue::Value(value.into()),
         }
     }
 
@@ -379,20 +483,20 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("age").greater_or_equal(18);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age >= 18 return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age >= @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
-    #[allow(clippy::needless_pass_by_value)]
     pub fn greater_or_equal<T>(self, value: T) -> Comparison
     where
-        T: Num + Display,
+        T: Num + Into<Value>,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: ">=".to_string(),
-            right_value: format!(r#"{}"#, value),
+            right_value: ComparisonValue::Value(value.into()),
         }
     }
 
@@ -406,20 +510,20 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("age").lesser_than(18);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age < 18 return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age < @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
-    #[allow(clippy::needless_pass_by_value)]
     pub fn lesser_than<T>(self, value: T) -> Comparison
     where
-        T: Num + Display,
+        T: Num + Into<Value>,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "<".to_string(),
-            right_value: format!(r#"{}"#, value),
+            right_value: ComparisonValue::Value(value.into()),
         }
     }
 
@@ -433,20 +537,20 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("age").lesser_or_equal(18);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age <= 18 return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age <= @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
-    #[allow(clippy::needless_pass_by_value)]
     pub fn lesser_or_equal<T>(self, value: T) -> Comparison
     where
-        T: Num + Display,
+        T: Num + Into<Value>,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "<=".to_string(),
-            right_value: format!(r#"{}"#, value),
+            right_value: ComparisonValue::Value(value.into()),
         }
     }
 
@@ -460,19 +564,20 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("age").in_array(&[1, 11, 16, 18]);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age IN [1, 11, 16, 18] return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age IN @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
     pub fn in_array<T>(self, array: &[T]) -> Comparison
     where
-        T: Display,
+        T: Clone + Into<Value>,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "IN".to_string(),
-            right_value: string_array_from_array(array),
+            right_value: ComparisonValue::Value(Value::from(array)),
         }
     }
 
@@ -486,19 +591,20 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("age").not_in_array(&[1, 11, 16, 18]);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age NOT IN [1, 11, 16, 18] return a");
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.age NOT IN @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
     pub fn not_in_array<T>(self, array: &[T]) -> Comparison
     where
-        T: Display,
+        T: Clone + Into<Value>,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "NOT IN".to_string(),
-            right_value: string_array_from_array(array),
+            right_value: ComparisonValue::Value(Value::from(array)),
         }
     }
 
@@ -512,7 +618,7 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("username").in_str_array(&["felix", "123felix"]);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Users FILTER a.username IN ["felix", "123felix"] return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.username IN @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
@@ -521,10 +627,16 @@ impl ComparisonBuilder {
         T: Display,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "IN".to_string(),
-            right_value: string_array_from_array_str(array),
+            right_value: ComparisonValue::Value(Value::from(
+                array
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>(),
+            )),
         }
     }
 
@@ -538,7 +650,7 @@ impl ComparisonBuilder {
     ///
     /// let query_item = Comparison::field("username").not_in_str_array(&["felix", "123felix"]);
     /// let query = Query::new("Users").filter(Filter::new(query_item));
-    /// assert_eq!(query.aql_str(), r#"FOR a in Users FILTER a.username NOT IN ["felix", "123felix"] return a"#);
+    /// assert_eq!(query.aql_str(), "FOR a in Users FILTER a.username NOT IN @aragog_0 return a");
     /// ```
     #[inline]
     #[must_use]
@@ -547,10 +659,16 @@ impl ComparisonBuilder {
         T: Display,
     {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "NOT IN".to_string(),
-            right_value: string_array_from_array_str(array),
+            right_value: ComparisonValue::Value(Value::from(
+                array
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>(),
+            )),
         }
     }
 
@@ -571,10 +689,11 @@ impl ComparisonBuilder {
     #[deprecated(since = "0.17.0", note = "use `eq_null` instead")]
     pub fn is_null(self) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "==".to_string(),
-            right_value: "null".to_string(),
+            right_value: ComparisonValue::Statement("null".to_string()),
         }
     }
 
@@ -593,10 +712,11 @@ impl ComparisonBuilder {
     #[must_use]
     pub fn eq_null(self) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "==".to_string(),
-            right_value: "null".to_string(),
+            right_value: ComparisonValue::Statement("null".to_string()),
         }
     }
 
@@ -615,10 +735,34 @@ impl ComparisonBuilder {
     #[must_use]
     pub fn not_null(self) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "!=".to_string(),
-            right_value: "null".to_string(),
+            right_value: ComparisonValue::Statement("null".to_string()),
+        }
+    }
+
+    /// Finalizes the current query item builder with a `comparator` and a JSON `value` of any type
+    pub(crate) fn compare_value(self, comparator: &str, value: Value) -> Comparison {
+        Comparison {
+            target: self.target,
+            function: None,
+            left_value: self.statement,
+            comparator: comparator.to_string(),
+            right_value: ComparisonValue::Value(value),
+        }
+    }
+
+    /// Finalizes the current query item builder with an equality comparison to an AQL `statement`,
+    /// rendered as is.
+    pub(crate) fn equals_statement(self, statement: &str) -> Comparison {
+        Comparison {
+            target: self.target,
+            function: None,
+            left_value: self.statement,
+            comparator: "==".to_string(),
+            right_value: ComparisonValue::Statement(statement.to_string()),
         }
     }
 
@@ -640,10 +784,11 @@ impl ComparisonBuilder {
     #[deprecated(since = "0.17.0", note = "use `eq_true` instead")]
     pub fn is_true(self) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "==".to_string(),
-            right_value: "true".to_string(),
+            right_value: ComparisonValue::Statement("true".to_string()),
         }
     }
 
@@ -663,10 +808,11 @@ impl ComparisonBuilder {
     #[must_use]
     pub fn eq_true(self) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "==".to_string(),
-            right_value: "true".to_string(),
+            right_value: ComparisonValue::Statement("true".to_string()),
         }
     }
 
@@ -688,10 +834,11 @@ impl ComparisonBuilder {
     #[deprecated(since = "0.17.0", note = "use `eq_false` instead")]
     pub fn is_false(self) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "==".to_string(),
-            right_value: "false".to_string(),
+            right_value: ComparisonValue::Statement("false".to_string()),
         }
     }
 
@@ -711,10 +858,113 @@ impl ComparisonBuilder {
     #[must_use]
     pub fn eq_false(self) -> Comparison {
         Comparison {
-            is_field: self.is_field,
+            target: self.target,
+            function: None,
             left_value: self.statement,
             comparator: "==".to_string(),
-            right_value: "false".to_string(),
+            right_value: ComparisonValue::Statement("false".to_string()),
+        }
+    }
+
+    /// Finalizes the current query item builder with a geo distance comparison: the field must
+    /// be a `GeoJSON` object or a `[longitude, latitude]` pair at most `meters` away from the given
+    /// point.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Filter, Query};
+    /// let query = Query::new("Stores")
+    ///     .filter(Comparison::field("location").within_distance(48.85, 2.35, 5000.0).into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in Stores FILTER GEO_DISTANCE(a.location, @aragog_1) <= @aragog_0 return a"
+    /// );
+    /// assert_eq!(query.bind_vars["aragog_0"], 5000.0);
+    /// assert_eq!(query.bind_vars["aragog_1"], serde_json::json!([2.35, 48.85]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn within_distance(self, latitude: f64, longitude: f64, meters: f64) -> Comparison {
+        Comparison {
+            target: self.target,
+            function: Some(ComparisonFunction::Distance(ComparisonValue::Value(
+                geo_point(latitude, longitude),
+            ))),
+            left_value: self.statement,
+            comparator: "<=".to_string(),
+            right_value: ComparisonValue::Value(Value::from(meters)),
+        }
+    }
+
+    /// Finalizes the current query item builder with a `GEO_CONTAINS` comparison: the field must
+    /// be inside the polygon defined by the `(latitude, longitude)` vertices.
+    /// The polygon is closed automatically if the last vertex differs from the first one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Filter};
+    /// let comparison = Comparison::field("location")
+    ///     .within_polygon(&[(48.0, 2.0), (49.0, 2.0), (49.0, 3.0)]);
+    /// assert_eq!(
+    ///     comparison.aql_str("i"),
+    ///     r#"GEO_CONTAINS({"coordinates":[[[2.0,48.0],[2.0,49.0],[3.0,49.0],[2.0,48.0]]],"type":"Polygon"}, i.location)"#
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn within_polygon(self, vertices: &[(f64, f64)]) -> Comparison {
+        let mut ring: Vec<Value> = vertices
+            .iter()
+            .map(|(latitude, longitude)| geo_point(*latitude, *longitude))
+            .collect();
+        if let (Some(first), Some(last)) = (ring.first(), ring.last()) {
+            if first != last {
+                ring.push(first.clone());
+            }
+        }
+        Comparison {
+            target: self.target,
+            function: Some(ComparisonFunction::Contains),
+            left_value: self.statement,
+            comparator: String::new(),
+            right_value: ComparisonValue::Value(json!({
+                "type": "Polygon",
+                "coordinates": [ring],
+            })),
+        }
+    }
+
+    /// Finalizes the current query item builder with a `GEO_INTERSECTS` comparison: the field
+    /// must intersect the given `GeoJSON` object.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Filter};
+    /// # use serde_json::json;
+    /// let comparison = Comparison::field("area").intersects(json!({
+    ///     "type": "LineString",
+    ///     "coordinates": [[2.0, 48.0], [3.0, 49.0]]
+    /// }));
+    /// assert_eq!(
+    ///     comparison.aql_str("i"),
+    ///     r#"GEO_INTERSECTS({"coordinates":[[2.0,48.0],[3.0,49.0]],"type":"LineString"}, i.area)"#
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn intersects<T>(self, geo_json: T) -> Comparison
+    where
+        T: Into<Value>,
+    {
+        Comparison {
+            target: self.target,
+            function: Some(ComparisonFunction::Intersects),
+            left_value: self.statement,
+            comparator: String::new(),
+            right_value: ComparisonValue::Value(geo_json.into()),
         }
     }
 }
@@ -735,7 +985,7 @@ impl Comparison {
     #[inline]
     pub fn field(field_name: &str) -> ComparisonBuilder {
         ComparisonBuilder {
-            is_field: true,
+            target: ComparisonTarget::Field,
             statement: field_name.to_string(),
         }
     }
@@ -757,7 +1007,7 @@ impl Comparison {
     #[inline]
     pub fn all(array_field_name: &str) -> ComparisonBuilder {
         ComparisonBuilder {
-            is_field: true,
+            target: ComparisonTarget::Field,
             statement: format!("{} ALL", array_field_name),
         }
     }
@@ -778,7 +1028,7 @@ impl Comparison {
     #[inline]
     pub fn none(array_field_name: &str) -> ComparisonBuilder {
         ComparisonBuilder {
-            is_field: true,
+            target: ComparisonTarget::Field,
             statement: format!("{} NONE", array_field_name),
         }
     }
@@ -799,11 +1049,119 @@ impl Comparison {
     #[inline]
     pub fn any(array_field_name: &str) -> ComparisonBuilder {
         ComparisonBuilder {
-            is_field: true,
+            target: ComparisonTarget::Field,
             statement: format!("{} ANY", array_field_name),
         }
     }
 
+    /// Instantiates a new builder for a `Comparison` on the `field_name` of every vertex of the
+    /// traversal path. All the path vertices will have to match the comparison to succeed.
+    ///
+    /// # Note
+    ///
+    /// Only works on graph queries, the path variable is declared automatically.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::outbound(1, 3, "Follows", "User/123")
+    ///     .filter(Comparison::all_path_vertices("active").eq_true().into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a, a_edge, a_path in 1..3 OUTBOUND 'User/123' Follows FILTER a_path.vertices[*].active ALL == true return a"
+    /// );
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn all_path_vertices(field_name: &str) -> ComparisonBuilder {
+        ComparisonBuilder {
+            target: ComparisonTarget::PathField,
+            statement: format!("vertices[*].{} ALL", field_name),
+        }
+    }
+
+    /// Instantiates a new builder for a `Comparison` on the `field_name` of every edge of the
+    /// traversal path. All the path edges will have to match the comparison to succeed.
+    ///
+    /// # Note
+    ///
+    /// Only works on graph queries, the path variable is declared automatically.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::outbound(1, 3, "Follows", "User/123")
+    ///     .filter(Comparison::all_path_edges("kind").equals_str("friend").into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a, a_edge, a_path in 1..3 OUTBOUND 'User/123' Follows FILTER a_path.edges[*].kind ALL == @aragog_0 return a"
+    /// );
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn all_path_edges(field_name: &str) -> ComparisonBuilder {
+        ComparisonBuilder {
+            target: ComparisonTarget::PathField,
+            statement: format!("edges[*].{} ALL", field_name),
+        }
+    }
+
+    /// Instantiates a new builder for a `Comparison` on the `field_name` of the path vertex at
+    /// `depth`, the start vertex being at depth `0`.
+    ///
+    /// # Note
+    ///
+    /// Only works on graph queries, the path variable is declared automatically.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::outbound(2, 2, "Follows", "User/123")
+    ///     .filter(Comparison::path_vertex(1, "age").greater_than(18).into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a, a_edge, a_path in 2..2 OUTBOUND 'User/123' Follows FILTER a_path.vertices[1].age > @aragog_0 return a"
+    /// );
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn path_vertex(depth: u16, field_name: &str) -> ComparisonBuilder {
+        ComparisonBuilder {
+            target: ComparisonTarget::PathField,
+            statement: format!("vertices[{}].{}", depth, field_name),
+        }
+    }
+
+    /// Instantiates a new builder for a `Comparison` on the `field_name` of the path edge at
+    /// `depth`, the first traversed edge being at depth `0`.
+    ///
+    /// # Note
+    ///
+    /// Only works on graph queries, the path variable is declared automatically.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::outbound(1, 3, "Follows", "User/123")
+    ///     .prune(Comparison::path_edge(0, "kind").different_than_str("friend").into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a, a_edge, a_path in 1..3 OUTBOUND 'User/123' Follows PRUNE a_path.edges[0].kind != @aragog_0 return a"
+    /// );
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn path_edge(depth: u16, field_name: &str) -> ComparisonBuilder {
+        ComparisonBuilder {
+            target: ComparisonTarget::PathField,
+            statement: format!("edges[{}].{}", depth, field_name),
+        }
+    }
+
     /// Instantiates a new builder for a `Comparison` with the specified `statement`.
     /// The field will be used as the left value of the comparison.
     ///
@@ -819,7 +1177,7 @@ impl Comparison {
     #[inline]
     pub fn statement(statement: &str) -> ComparisonBuilder {
         ComparisonBuilder {
-            is_field: false,
+            target: ComparisonTarget::Statement,
             statement: statement.to_string(),
         }
     }
@@ -884,15 +1242,133 @@ impl Comparison {
     /// ```
     #[must_use]
     pub fn aql_str(&self, collection_id: &str) -> String {
-        let id = if self.is_field {
-            format!("{}.", collection_id)
-        } else {
-            String::new()
+        let id = match self.target {
+            ComparisonTarget::Field => format!("{}.", collection_id),
+            ComparisonTarget::PathField => format!("{}.", traversal_variables(collection_id).1),
+            ComparisonTarget::Statement => String::new(),
         };
-        format!(
-            "{}{} {} {}",
-            id, &self.left_value, &self.comparator, &self.right_value
-        )
+        let left_value = format!("{}{}", id, &self.left_value);
+        match &self.function {
+            None => format!("{} {} {}", left_value, &self.comparator, &self.right_value),
+            Some(ComparisonFunction::Distance(point)) => format!(
+                "GEO_DISTANCE({}, {}) {} {}",
+                left_value, point, &self.comparator, &self.right_value
+            ),
+            Some(ComparisonFunction::Contains) => {
+                format!("GEO_CONTAINS({}, {})", &self.right_value, left_value)
+            }
+            Some(ComparisonFunction::Intersects) => {
+                format!("GEO_INTERSECTS({}, {})", &self.right_value, left_value)
+            }
+        }
+    }
+
+    /// Checks if the `document` matches the comparison without querying the database, following
+    /// the AQL comparison semantics:
+    /// - Values of different types are ordered `null < bool < number < string < array < object`
+    /// - Missing attributes are `null`
+    /// - `LIKE` and regular expression comparisons convert the value to a string
+    ///
+    /// # Note
+    ///
+    /// Strings are compared by their bytes, regardless of the server locale, and the geo
+    /// comparisons use planar polygons instead of geodesic ones.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the comparison is a [`statement`] or a path comparison, if its value was
+    /// registered as a query bind parameter or if a regular expression is invalid
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Comparison;
+    /// # use serde_json::json;
+    /// let document = json!({ "name": "Felix", "age": 31, "emails": ["felix@mail.com"] });
+    /// assert!(Comparison::field("age").greater_than(18).evaluate(&document).unwrap());
+    /// assert!(Comparison::field("name").like("F%").evaluate(&document).unwrap());
+    /// assert!(Comparison::any("emails").like("%@mail.com").evaluate(&document).unwrap());
+    /// // `null` is lower than any number
+    /// assert!(Comparison::field("height").lesser_than(180).evaluate(&document).unwrap());
+    /// ```
+    ///
+    /// [`statement`]: Self::statement
+    pub fn evaluate(&self, document: &Value) -> Result<bool, Error> {
+        self.evaluate_in(document, None)
+    }
+
+    /// Checks if the `document` matches the comparison, path comparisons being evaluated against
+    /// the traversal `path` object (`{ "vertices": [...], "edges": [...] }`)
+    pub(crate) fn evaluate_in(&self, document: &Value, path: Option<&Value>) -> Result<bool, Error> {
+        let (statement, quantifier) = split_quantifier(&self.left_value);
+        let left = match (self.target, path) {
+            (ComparisonTarget::Field, _) => resolve(document, statement)?,
+            (ComparisonTarget::PathField, Some(path)) => resolve(path, statement)?,
+            (ComparisonTarget::PathField, None) => {
+                return Err(Error::ValidationError(format!(
+                    "Can't evaluate the `{}` path comparison without traversal path",
+                    self.left_value
+                )))
+            }
+            (ComparisonTarget::Statement, _) => {
+                return Err(Error::ValidationError(format!(
+                    "Can't evaluate the `{}` statement",
+                    self.left_value
+                )))
+            }
+        };
+        let right = self.right_value.evaluated()?;
+        let point = match &self.function {
+            Some(ComparisonFunction::Distance(point)) => Some(point.evaluated()?),
+            _ => None,
+        };
+        let test = |left: &Value| match &self.function {
+            None => compare(&self.comparator, left, &right),
+            Some(ComparisonFunction::Distance(_)) => compare(
+                &self.comparator,
+                &geo_distance(left, point.as_ref().unwrap_or(&Value::Null)),
+                &right,
+            ),
+            Some(ComparisonFunction::Contains) => Ok(geo_contains(&right, left)),
+            Some(ComparisonFunction::Intersects) => Ok(geo_intersects(&right, left)),
+        };
+        match (quantifier, left.as_array()) {
+            (None, _) => test(&left),
+            // Array comparison operators are false for non array values
+            (Some(_), None) => Ok(false),
+            (Some(Quantifier::All), Some(items)) => items
+                .iter()
+                .try_fold(true, |res, item| Ok(res && test(item)?)),
+            (Some(Quantifier::Any), Some(items)) => items
+                .iter()
+                .try_fold(false, |res, item| Ok(res || test(item)?)),
+            (Some(Quantifier::None), Some(items)) => items
+                .iter()
+                .try_fold(true, |res, item| Ok(res && !test(item)?)),
+        }
+    }
+
+    /// Whether the comparison uses the traversal path variable
+    pub(crate) const fn uses_path(&self) -> bool {
+        matches!(self.target, ComparisonTarget::PathField)
+    }
+
+    /// Registers the compared value in `bind_vars` under a generated name, if not already done.
+    pub(crate) fn bind_value(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        self.right_value.bind(bind_vars);
+        if let Some(ComparisonFunction::Distance(point)) = &mut self.function {
+            point.bind(bind_vars);
+        }
+    }
+
+    /// Takes back the compared value from `bind_vars`, reverting [`bind_value`].
+    ///
+    /// [`bind_value`]: Self::bind_value
+    pub(crate) fn unbind_value(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        self.right_value.unbind(bind_vars);
+        if let Some(ComparisonFunction::Distance(point)) = &mut self.function {
+            point.unbind(bind_vars);
+        }
     }
 }
 
@@ -0,0 +1,385 @@
+use std::cmp::Ordering;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::Error;
+
+/// Earth radius used by `ArangoDB` for `GEO_DISTANCE`, in meters
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+/// Array comparison operator of a comparison left value (`field ALL`, `field ANY`, `field NONE`)
+#[derive(Copy, Clone, Debug)]
+pub enum Quantifier {
+    All,
+    Any,
+    None,
+}
+
+/// A `[longitude, latitude]` coordinate pair
+type Point = (f64, f64);
+
+/// A simple geometry of a `GeoJSON` object
+enum Shape {
+    Point(Point),
+    Line(Vec<Point>),
+    Polygon(Vec<Vec<Point>>),
+}
+
+/// Splits the array comparison operator from a comparison left `statement`
+pub fn split_quantifier(statement: &str) -> (&str, Option<Quantifier>) {
+    [
+        (" ALL", Quantifier::All),
+        (" ANY", Quantifier::Any),
+        (" NONE", Quantifier::None),
+    ]
+    .iter()
+    .find_map(|(suffix, quantifier)| {
+        statement
+            .strip_suffix(suffix)
+            .map(|field| (field, Some(*quantifier)))
+    })
+    .unwrap_or((statement, None))
+}
+
+/// Retrieves the value at the attribute `path` of `document`, like `address.city`, `tags[0]` or
+/// `emails[*].address`. Missing attributes are `null`, like in AQL.
+pub fn resolve(document: &Value, path: &str) -> Result<Value, Error> {
+    let invalid_path = || Error::ValidationError(format!("Can't evaluate the field `{}`", path));
+    let mut current = document.clone();
+    let mut rest = path;
+    while !rest.is_empty() {
+        if let Some(stripped) = rest.strip_prefix('[') {
+            let end = stripped.find(']').ok_or_else(invalid_path)?;
+            let index = &stripped[..end];
+            rest = &stripped[end + 1..];
+            if index == "*" {
+                let rest_path = rest.strip_prefix('.').unwrap_or(rest);
+                let items = current.as_array().cloned().unwrap_or_default();
+                return items
+                    .iter()
+                    .map(|item| resolve(item, rest_path))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::from);
+            }
+            let index: i64 = index.trim().parse().map_err(|_| invalid_path())?;
+            current = current.as_array().map_or(Value::Null, |items| {
+                let index = if index < 0 {
+                    i64::try_from(items.len()).unwrap_or(i64::MAX) + index
+                } else {
+                    index
+                };
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|index| items.get(index))
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            });
+        } else {
+            let rest_path = rest.strip_prefix('.').unwrap_or(rest);
+            let end = rest_path.find(&['.', '['][..]).unwrap_or(rest_path.len());
+            let attribute = rest_path[..end].trim_matches('`');
+            if attribute.is_empty() {
+                return Err(invalid_path());
+            }
+            current = current.get(attribute).cloned().unwrap_or(Value::Null);
+            rest = &rest_path[end..];
+        }
+    }
+    Ok(current)
+}
+
+/// Rank of the type of `value` in the AQL type order: null < bool < number < string < array < object
+const fn type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+/// Compares two values following the AQL ordering: values of different types are ordered by
+/// type, arrays element by element and objects attribute by attribute, missing elements and
+/// attributes being `null`. Strings are compared by their bytes.
+pub fn aql_cmp(left: &Value, right: &Value) -> Ordering {
+    match (left, right) {
+        (Value::Bool(left), Value::Bool(right)) => left.cmp(right),
+        (Value::Number(left), Value::Number(right)) => {
+            let (left, right) = (
+                left.as_f64().unwrap_or_default(),
+                right.as_f64().unwrap_or_default(),
+            );
+            left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+        }
+        (Value::String(left), Value::String(right)) => left.cmp(right),
+        (Value::Array(left), Value::Array(right)) => (0..left.len().max(right.len()))
+            .map(|i| {
+                aql_cmp(
+                    left.get(i).unwrap_or(&Value::Null),
+                    right.get(i).unwrap_or(&Value::Null),
+                )
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal),
+        (Value::Object(left), Value::Object(right)) => {
+            let mut keys: Vec<&String> = left.keys().chain(right.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            keys.into_iter()
+                .map(|key| {
+                    aql_cmp(
+                        left.get(key).unwrap_or(&Value::Null),
+                        right.get(key).unwrap_or(&Value::Null),
+                    )
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        }
+        _ => type_rank(left).cmp(&type_rank(right)),
+    }
+}
+
+/// Converts `value` to a string like AQL does for string operators
+fn aql_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Checks if `value` matches the `LIKE` `pattern`, `%` matching any sequence and `_` any
+/// character. Wildcards can be escaped with a backslash.
+fn like(value: &str, pattern: &str) -> Result<bool, Error> {
+    let mut regex = String::from("(?s)^");
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    regex.push_str(&regex::escape(&escaped.to_string()));
+                }
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex_matches(value, &regex)
+}
+
+/// Checks if `value` matches the regular expression `pattern` anywhere
+fn regex_matches(value: &str, pattern: &str) -> Result<bool, Error> {
+    let regex = Regex::new(pattern).map_err(|error| {
+        Error::ValidationError(format!(
+            "Invalid regular expression `{}`: {}",
+            pattern, error
+        ))
+    })?;
+    Ok(regex.is_match(value))
+}
+
+/// Applies the AQL `comparator` to `left` and `right`
+pub fn compare(comparator: &str, left: &Value, right: &Value) -> Result<bool, Error> {
+    let is_in = || {
+        right.as_array().map_or(false, |items| {
+            items.iter().any(|item| aql_cmp(left, item).is_eq())
+        })
+    };
+    Ok(match comparator {
+        "==" => aql_cmp(left, right).is_eq(),
+        "!=" => aql_cmp(left, right).is_ne(),
+        "<" => aql_cmp(left, right).is_lt(),
+        "<=" => aql_cmp(left, right).is_le(),
+        ">" => aql_cmp(left, right).is_gt(),
+        ">=" => aql_cmp(left, right).is_ge(),
+        "IN" => is_in(),
+        "NOT IN" => !is_in(),
+        "LIKE" => like(&aql_string(left), &aql_string(right))?,
+        "NOT LIKE" => !like(&aql_string(left), &aql_string(right))?,
+        "=~" => regex_matches(&aql_string(left), &aql_string(right))?,
+        "!~" => !regex_matches(&aql_string(left), &aql_string(right))?,
+        comparator => {
+            return Err(Error::ValidationError(format!(
+                "Can't evaluate the `{}` comparator",
+                comparator
+            )))
+        }
+    })
+}
+
+/// Parses a `[longitude, latitude]` coordinate pair
+fn parse_point(value: &Value) -> Option<Point> {
+    match value.as_array()?.as_slice() {
+        [longitude, latitude] => Some((longitude.as_f64()?, latitude.as_f64()?)),
+        _ => None,
+    }
+}
+
+/// Parses a list of coordinate pairs
+fn parse_points(value: &Value) -> Option<Vec<Point>> {
+    value.as_array()?.iter().map(parse_point).collect()
+}
+
+/// Parses a list of coordinate pair lists
+fn parse_rings(value: &Value) -> Option<Vec<Vec<Point>>> {
+    value.as_array()?.iter().map(parse_points).collect()
+}
+
+/// Parses the simple geometries of a `GeoJSON` object or of a coordinate pair
+fn parse_shapes(value: &Value) -> Option<Vec<Shape>> {
+    if let Some(point) = parse_point(value) {
+        return Some(vec![Shape::Point(point)]);
+    }
+    let coordinates = value.get("coordinates")?;
+    Some(match value.get("type")?.as_str()? {
+        "Point" => vec![Shape::Point(parse_point(coordinates)?)],
+        "MultiPoint" => parse_points(coordinates)?
+            .into_iter()
+            .map(Shape::Point)
+            .collect(),
+        "LineString" => vec![Shape::Line(parse_points(coordinates)?)],
+        "MultiLineString" => parse_rings(coordinates)?
+            .into_iter()
+            .map(Shape::Line)
+            .collect(),
+        "Polygon" => vec![Shape::Polygon(parse_rings(coordinates)?)],
+        "MultiPolygon" => coordinates
+            .as_array()?
+            .iter()
+            .map(|polygon| parse_rings(polygon).map(Shape::Polygon))
+            .collect::<Option<_>>()?,
+        _ => return None,
+    })
+}
+
+impl Shape {
+    fn vertices(&self) -> Vec<Point> {
+        match self {
+            Self::Point(point) => vec![*point],
+            Self::Line(points) => points.clone(),
+            Self::Polygon(rings) => rings.iter().flatten().copied().collect(),
+        }
+    }
+
+    fn segments(&self) -> Vec<(Point, Point)> {
+        match self {
+            Self::Point(point) => vec![(*point, *point)],
+            Self::Line(points) => points.windows(2).map(|w| (w[0], w[1])).collect(),
+            Self::Polygon(rings) => rings
+                .iter()
+                .flat_map(|ring| ring.windows(2).map(|w| (w[0], w[1])))
+                .collect(),
+        }
+    }
+
+    /// Whether `point` is inside the polygon, holes excluded
+    fn contains_point(&self, point: Point) -> bool {
+        let rings = match self {
+            Self::Polygon(rings) => rings,
+            _ => return false,
+        };
+        let (x, y) = point;
+        let mut inside = false;
+        for (a, b) in rings
+            .iter()
+            .flat_map(|ring| ring.windows(2).map(|w| (w[0], w[1])))
+        {
+            if (a.1 > y) != (b.1 > y) && x < (b.0 - a.0) * (y - a.1) / (b.1 - a.1) + a.0 {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        let other_segments = other.segments();
+        self.segments().iter().any(|segment| {
+            other_segments
+                .iter()
+                .any(|other_segment| segments_intersect(*segment, *other_segment))
+        }) || other
+            .vertices()
+            .iter()
+            .any(|point| self.contains_point(*point))
+            || self
+                .vertices()
+                .iter()
+                .any(|point| other.contains_point(*point))
+    }
+}
+
+/// Orientation of the `c` point relatively to the `a` -> `b` vector
+fn orientation(a: Point, b: Point, c: Point) -> f64 {
+    (b.0 - a.0).mul_add(c.1 - a.1, -(b.1 - a.1) * (c.0 - a.0))
+}
+
+/// Whether `point` is in the bounding box of the `segment`
+fn in_box(segment: (Point, Point), point: Point) -> bool {
+    let (a, b) = segment;
+    point.0 >= a.0.min(b.0)
+        && point.0 <= a.0.max(b.0)
+        && point.1 >= a.1.min(b.1)
+        && point.1 <= a.1.max(b.1)
+}
+
+fn segments_intersect(first: (Point, Point), second: (Point, Point)) -> bool {
+    let o1 = orientation(first.0, first.1, second.0);
+    let o2 = orientation(first.0, first.1, second.1);
+    let o3 = orientation(second.0, second.1, first.0);
+    let o4 = orientation(second.0, second.1, first.1);
+    if o1 * o2 < 0.0 && o3 * o4 < 0.0 {
+        return true;
+    }
+    (o1.abs() < f64::EPSILON && in_box(first, second.0))
+        || (o2.abs() < f64::EPSILON && in_box(first, second.1))
+        || (o3.abs() < f64::EPSILON && in_box(second, first.0))
+        || (o4.abs() < f64::EPSILON && in_box(second, first.1))
+}
+
+/// `GEO_DISTANCE` between two points in meters, `null` if one of them is not a point
+pub fn geo_distance(left: &Value, right: &Value) -> Value {
+    let shape_point = |value: &Value| match parse_shapes(value)?.as_slice() {
+        [Shape::Point(point)] => Some(*point),
+        _ => None,
+    };
+    match (shape_point(left), shape_point(right)) {
+        (Some((lon1, lat1)), Some((lon2, lat2))) => {
+            let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+            let (d_lat, d_lon) = (lat2 - lat1, (lon2 - lon1).to_radians());
+            let a = (lat1.cos() * lat2.cos())
+                .mul_add((d_lon / 2.0).sin().powi(2), (d_lat / 2.0).sin().powi(2));
+            Value::from(2.0 * EARTH_RADIUS * a.sqrt().asin())
+        }
+        _ => Value::Null,
+    }
+}
+
+/// `GEO_CONTAINS`: whether every vertex of the `shapes` is inside one of the `polygons`
+pub fn geo_contains(polygons: &Value, shapes: &Value) -> bool {
+    match (parse_shapes(polygons), parse_shapes(shapes)) {
+        (Some(polygons), Some(shapes)) => shapes.iter().all(|shape| {
+            polygons.iter().any(|polygon| {
+                shape
+                    .vertices()
+                    .iter()
+                    .all(|point| polygon.contains_point(*point))
+            })
+        }),
+        _ => false,
+    }
+}
+
+/// `GEO_INTERSECTS`: whether the two geometries share a point
+pub fn geo_intersects(left: &Value, right: &Value) -> bool {
+    match (parse_shapes(left), parse_shapes(right)) {
+        (Some(left), Some(right)) => left
+            .iter()
+            .any(|shape| right.iter().any(|other| shape.intersects(other))),
+        _ => false,
+    }
+}
@@ -0,0 +1,364 @@
+use std::marker::PhantomData;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::query::{Comparison, ComparisonBuilder};
+
+/// Types providing typed field paths, implemented by the `Record` and `Fields` derive macros.
+///
+/// The generated `<Type>Fields` struct has a method per serialized field, returning a [`Field`]
+/// or the paths of the nested object. The serde `rename`, `rename_all`, `flatten` and `skip`
+/// attributes are honored, nested objects must be marked with `#[nested_fields]` and derive
+/// `Fields`.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::{Fields, Record};
+/// # use aragog::query::{Fields as _, Query};
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Clone, Serialize, Deserialize, Fields)]
+/// pub struct Address {
+///     pub city: String,
+/// }
+///
+/// #[derive(Clone, Serialize, Deserialize, Record)]
+/// #[serde(rename_all = "camelCase")]
+/// pub struct User {
+///     pub first_name: String,
+///     pub age: u16,
+///     #[nested_fields]
+///     pub address: Address,
+/// }
+///
+/// let query = User::query()
+///     .filter(User::fields().age().greater_or_equal(18).into())
+///     .filter(User::fields().address().city().equals("Paris").into())
+///     .sort(User::fields().first_name().path(), None);
+/// assert_eq!(
+///     query.aql_str(),
+///     "FOR a in User FILTER a.age >= @aragog_0 FILTER a.address.city == @aragog_1 SORT a.firstName ASC return a"
+/// );
+/// ```
+pub trait Fields {
+    /// The generated field paths type
+    type Paths;
+
+    /// Instantiates the field paths of an object at `prefix`, or at the document root if
+    /// `prefix` is empty
+    fn field_paths(prefix: &str) -> Self::Paths;
+
+    /// Instantiates the field paths of the document root
+    #[must_use]
+    fn fields() -> Self::Paths {
+        Self::field_paths("")
+    }
+}
+
+impl<T: Fields> Fields for Option<T> {
+    type Paths = T::Paths;
+
+    fn field_paths(prefix: &str) -> Self::Paths {
+        T::field_paths(prefix)
+    }
+}
+
+impl<T: Fields> Fields for Box<T> {
+    type Paths = T::Paths;
+
+    fn field_paths(prefix: &str) -> Self::Paths {
+        T::field_paths(prefix)
+    }
+}
+
+/// Typed path of a document field of type `T`, as returned by the [`Fields`] paths.
+///
+/// The available comparison operators depend on `T`: every serializable field supports
+/// equality, array and null comparisons, numeric and string fields support ordering, string
+/// fields support patterns, boolean fields support `eq_true`/`eq_false`, and array fields
+/// provide the `ALL`, `ANY` and `NONE` quantifiers over their items.
+pub struct Field<T> {
+    path: String,
+    builder: ComparisonBuilder,
+    value_type: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            builder: self.builder.clone(),
+            value_type: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Field<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Field")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Field<T> {
+    /// Instantiates the field at `path`, like `address.city`
+    #[inline]
+    #[must_use]
+    pub fn new(path: &str) -> Self {
+        Self::with_builder(path, Comparison::field(path))
+    }
+
+    /// Instantiates the `field` of the object at `prefix`, or at the document root if `prefix`
+    /// is empty
+    #[inline]
+    #[must_use]
+    pub fn nested(prefix: &str, field: &str) -> Self {
+        if prefix.is_empty() {
+            Self::new(field)
+        } else {
+            Self::new(&format!("{}.{}", prefix, field))
+        }
+    }
+
+    fn with_builder(path: &str, builder: ComparisonBuilder) -> Self {
+        Self {
+            path: path.to_string(),
+            builder,
+            value_type: PhantomData,
+        }
+    }
+
+    /// The field path, to use in sorts or projections
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The untyped comparison builder of the field
+    #[inline]
+    #[must_use]
+    pub fn builder(self) -> ComparisonBuilder {
+        self.builder
+    }
+
+    /// Finalizes the comparison with a `== null` check, for optional fields
+    #[inline]
+    #[must_use]
+    pub fn is_null(self) -> Comparison {
+        self.builder.eq_null()
+    }
+
+    /// Finalizes the comparison with a `!= null` check, for optional fields
+    #[inline]
+    #[must_use]
+    pub fn not_null(self) -> Comparison {
+        self.builder.not_null()
+    }
+
+    /// Finalizes the comparison with an equality check
+    #[inline]
+    #[must_use]
+    pub fn equals(self, value: impl FieldValue<T>) -> Comparison {
+        self.builder.equals(value.field_value())
+    }
+
+    /// Finalizes the comparison with an inequality check
+    #[inline]
+    #[must_use]
+    pub fn different_than(self, value: impl FieldValue<T>) -> Comparison {
+        self.builder.different_than(value.field_value())
+    }
+}
+
+impl<T> From<Field<T>> for ComparisonBuilder {
+    fn from(field: Field<T>) -> Self {
+        field.builder
+    }
+}
+
+/// Values which can be compared to a [`Field`] of type `T`: `T` values, `T` values for
+/// `Option<T>` fields and string slices for `String` fields
+pub trait FieldValue<T> {
+    /// Serializes the value, the values which can't be serialized are compared to `null`
+    fn field_value(self) -> Value;
+}
+
+/// Serializes a compared value, the values which can't be serialized are compared to `null`
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+impl<T: Serialize> FieldValue<T> for T {
+    fn field_value(self) -> Value {
+        to_value(&self)
+    }
+}
+
+impl<T: Serialize> FieldValue<Option<T>> for T {
+    fn field_value(self) -> Value {
+        to_value(&self)
+    }
+}
+
+impl FieldValue<String> for &str {
+    fn field_value(self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl FieldValue<Option<String>> for &str {
+    fn field_value(self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl<T: Serialize> Field<T> {
+    /// Finalizes the comparison with an `IN` check
+    #[inline]
+    #[must_use]
+    pub fn in_array(self, values: &[T]) -> Comparison {
+        let values: Vec<Value> = values.iter().map(to_value).collect();
+        self.builder.in_array(&values)
+    }
+
+    /// Finalizes the comparison with a `NOT IN` check
+    #[inline]
+    #[must_use]
+    pub fn not_in_array(self, values: &[T]) -> Comparison {
+        let values: Vec<Value> = values.iter().map(to_value).collect();
+        self.builder.not_in_array(&values)
+    }
+}
+
+impl<T> Field<Vec<T>> {
+    /// Compares the items of the array field, every item must match the comparison
+    #[inline]
+    #[must_use]
+    pub fn all(self) -> Field<T> {
+        Field::with_builder(&self.path, Comparison::all(&self.path))
+    }
+
+    /// Compares the items of the array field, at least one item must match the comparison
+    #[inline]
+    #[must_use]
+    pub fn any(self) -> Field<T> {
+        Field::with_builder(&self.path, Comparison::any(&self.path))
+    }
+
+    /// Compares the items of the array field, no item can match the comparison
+    #[inline]
+    #[must_use]
+    pub fn none(self) -> Field<T> {
+        Field::with_builder(&self.path, Comparison::none(&self.path))
+    }
+}
+
+/// Implements the ordering comparisons on the fields of the given types and their options
+macro_rules! impl_ordering {
+    ($($field_type:ty),*) => {
+        $(
+            impl_ordering!(@impl $field_type);
+            impl_ordering!(@impl Option<$field_type>);
+        )*
+    };
+    (@impl $field_type:ty) => {
+        impl Field<$field_type> {
+            /// Finalizes the comparison with a `>` check
+            #[inline]
+            #[must_use]
+            pub fn greater_than(self, value: impl FieldValue<$field_type>) -> Comparison {
+                self.builder.compare_value(">", value.field_value())
+            }
+
+            /// Finalizes the comparison with a `>=` check
+            #[inline]
+            #[must_use]
+            pub fn greater_or_equal(self, value: impl FieldValue<$field_type>) -> Comparison {
+                self.builder.compare_value(">=", value.field_value())
+            }
+
+            /// Finalizes the comparison with a `<` check
+            #[inline]
+            #[must_use]
+            pub fn lesser_than(self, value: impl FieldValue<$field_type>) -> Comparison {
+                self.builder.compare_value("<", value.field_value())
+            }
+
+            /// Finalizes the comparison with a `<=` check
+            #[inline]
+            #[must_use]
+            pub fn lesser_or_equal(self, value: impl FieldValue<$field_type>) -> Comparison {
+                self.builder.compare_value("<=", value.field_value())
+            }
+        }
+    };
+}
+
+impl_ordering!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, String);
+
+/// Implements the pattern comparisons on the string fields
+macro_rules! impl_patterns {
+    ($($field_type:ty),*) => {
+        $(
+            impl Field<$field_type> {
+                /// Finalizes the comparison with a `LIKE` pattern check
+                #[inline]
+                #[must_use]
+                pub fn like(self, pattern: &str) -> Comparison {
+                    self.builder.like(pattern)
+                }
+
+                /// Finalizes the comparison with a `NOT LIKE` pattern check
+                #[inline]
+                #[must_use]
+                pub fn not_like(self, pattern: &str) -> Comparison {
+                    self.builder.not_like(pattern)
+                }
+
+                /// Finalizes the comparison with a `=~` regular expression check
+                #[inline]
+                #[must_use]
+                pub fn matches(self, regular_expression: &str) -> Comparison {
+                    self.builder.matches(regular_expression)
+                }
+
+                /// Finalizes the comparison with a `!~` regular expression check
+                #[inline]
+                #[must_use]
+                pub fn does_not_match(self, regular_expression: &str) -> Comparison {
+                    self.builder.does_not_match(regular_expression)
+                }
+            }
+        )*
+    };
+}
+
+impl_patterns!(String, Option<String>);
+
+/// Implements the boolean comparisons on the boolean fields
+macro_rules! impl_boolean {
+    ($($field_type:ty),*) => {
+        $(
+            impl Field<$field_type> {
+                /// Finalizes the comparison with a `== true` check
+                #[inline]
+                #[must_use]
+                pub fn eq_true(self) -> Comparison {
+                    self.builder.eq_true()
+                }
+
+                /// Finalizes the comparison with a `== false` check
+                #[inline]
+                #[must_use]
+                pub fn eq_false(self) -> Comparison {
+                    self.builder.eq_false()
+                }
+            }
+        )*
+    };
+}
+
+impl_boolean!(bool, Option<bool>);
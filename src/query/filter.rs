@@ -1,15 +1,22 @@
-use std::fmt::{Display, Formatter, Result};
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Not;
+
+use serde::Serialize;
+use serde_json::Value;
 
 use crate::query::Comparison;
+use crate::Error;
 
+/// Logical operator between two conditions
 #[derive(Clone, Debug)]
-enum Operator {
+pub enum Operator {
     And,
     Or,
 }
 
 impl Display for Operator {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "{}",
@@ -21,11 +28,49 @@ impl Display for Operator {
     }
 }
 
+/// Condition of a [`Filter`]
+#[derive(Clone, Debug)]
+enum FilterItem {
+    Comparison(Comparison),
+    /// Nested filter, always rendered between parentheses
+    Group(Filter),
+}
+
 /// Allows to filter a query according to different [`Comparison`].
+///
+/// The conditions are chained linearly with [`and`] and [`or`], following the AQL operator
+/// precedence (`&&` before `||`). Use [`and_filter`], [`or_filter`] and [`group`] to nest
+/// filters between parentheses and `!` to negate a filter.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::query::{Comparison, Filter};
+/// // (A || B) && NOT (C && D)
+/// let filter = Filter::group(
+///     Filter::new(Comparison::field("owner").equals_str("felix"))
+///         .or(Comparison::field("public").eq_true()),
+/// )
+/// .and_filter(
+///     !Filter::new(Comparison::field("archived").eq_true())
+///         .and(Comparison::field("locked").eq_true()),
+/// );
+/// assert_eq!(
+///     filter.aql_str("i"),
+///     r#"(i.owner == "felix" || i.public == true) && NOT (i.archived == true && i.locked == true)"#
+/// );
+/// ```
+///
+/// [`and`]: Self::and
+/// [`or`]: Self::or
+/// [`and_filter`]: Self::and_filter
+/// [`or_filter`]: Self::or_filter
+/// [`group`]: Self::group
 #[derive(Clone, Debug)]
 pub struct Filter {
-    comparisons: Vec<Comparison>,
+    items: Vec<FilterItem>,
     operators: Vec<Operator>,
+    negated: bool,
 }
 
 impl Filter {
@@ -41,8 +86,31 @@ impl Filter {
     #[inline]
     pub fn new(comparison: Comparison) -> Self {
         Self {
-            comparisons: vec![comparison],
+            items: vec![FilterItem::Comparison(comparison)],
+            operators: vec![],
+            negated: false,
+        }
+    }
+
+    /// Instantiates a new query filter starting with the `filter` condition(s) between parentheses
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Filter};
+    /// let filter = Filter::group(
+    ///     Filter::new(Comparison::field("age").lesser_than(18)).or(Comparison::field("age").greater_than(65)),
+    /// )
+    /// .and(Comparison::field("active").eq_true());
+    /// assert_eq!(filter.aql_str("i"), "(i.age < 18 || i.age > 65) && i.active == true");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn group(filter: Self) -> Self {
+        Self {
+            items: vec![FilterItem::Group(filter)],
             operators: vec![],
+            negated: false,
         }
     }
 
@@ -59,7 +127,7 @@ impl Filter {
     #[must_use]
     #[inline]
     pub fn and(mut self, comparison: Comparison) -> Self {
-        self.comparisons.push(comparison);
+        self.items.push(FilterItem::Comparison(comparison));
         self.operators.push(Operator::And);
         self
     }
@@ -77,11 +145,70 @@ impl Filter {
     #[must_use]
     #[inline]
     pub fn or(mut self, comparison: Comparison) -> Self {
-        self.comparisons.push(comparison);
+        self.items.push(FilterItem::Comparison(comparison));
         self.operators.push(Operator::Or);
         self
     }
 
+    /// Appends the filter current condition(s) with the `filter` condition(s) between parentheses
+    /// with a `AND` logic.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Filter};
+    /// let filter = Filter::new(Comparison::field("active").eq_true()).and_filter(
+    ///     Filter::new(Comparison::field("age").lesser_than(18)).or(Comparison::field("age").greater_than(65)),
+    /// );
+    /// assert_eq!(filter.aql_str("i"), "i.active == true && (i.age < 18 || i.age > 65)");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn and_filter(mut self, filter: Self) -> Self {
+        self.items.push(FilterItem::Group(filter));
+        self.operators.push(Operator::And);
+        self
+    }
+
+    /// Appends the filter current condition(s) with the `filter` condition(s) between parentheses
+    /// with a `OR` logic.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Filter};
+    /// let filter = Filter::new(Comparison::field("admin").eq_true()).or_filter(
+    ///     !Filter::new(Comparison::field("banned").eq_true()),
+    /// );
+    /// assert_eq!(filter.aql_str("i"), "i.admin == true || NOT (i.banned == true)");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn or_filter(mut self, filter: Self) -> Self {
+        self.items.push(FilterItem::Group(filter));
+        self.operators.push(Operator::Or);
+        self
+    }
+
+    /// Negates the whole filter, rendered as `NOT (...)`, like the `!` operator.
+    /// Negating twice gives back the original filter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Filter};
+    /// let filter = Filter::new(Comparison::field("age").greater_than(10))
+    ///     .and(Comparison::field("age").lesser_than(20))
+    ///     .negate();
+    /// assert_eq!(filter.aql_str("i"), "NOT (i.age > 10 && i.age < 20)");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn negate(mut self) -> Self {
+        self.negated = !self.negated;
+        self
+    }
+
     /// Renders the AQL string corresponding to the current `Filter`. The query will go out of scope.
     ///
     /// # Example
@@ -90,7 +217,7 @@ impl Filter {
     /// # use aragog::query::{Comparison, Filter};
     /// let mut filter = Filter::new(Comparison::field("age").greater_than(10)).
     ///     or(Comparison::field("username").in_str_array(&["felix", "felixm"]));
-    /// assert_eq!(filter.to_aql("i"), String::from(r#"i.age > 10 || i.username IN ["felix", "felixm"]"#));
+    /// assert_eq!(filter.to_aql("i"), String::from(r#"i.age > 10 || i.username IN ["felix","felixm"]"#));
     /// ```
     #[must_use]
     #[deprecated(since = "0.17.0", note = "use `aql_str` instead")]
@@ -106,24 +233,149 @@ impl Filter {
     /// # use aragog::query::{Comparison, Filter};
     /// let mut filter = Filter::new(Comparison::field("age").greater_than(10)).
     ///     or(Comparison::field("username").in_str_array(&["felix", "felixm"]));
-    /// assert_eq!(filter.aql_str("i"), String::from(r#"i.age > 10 || i.username IN ["felix", "felixm"]"#));
+    /// assert_eq!(filter.aql_str("i"), String::from(r#"i.age > 10 || i.username IN ["felix","felixm"]"#));
     /// ```
     #[must_use]
     pub fn aql_str(&self, collection_id: &str) -> String {
         let mut res = String::new();
-        for (i, comparison) in self.comparisons.iter().enumerate() {
+        for (i, item) in self.items.iter().enumerate() {
             let operator_str = if i >= self.operators.len() {
                 String::new()
             } else {
                 format!(" {}", self.operators[i])
             };
-            res = format!(
-                "{} {}{}",
-                res,
-                comparison.aql_str(collection_id),
-                operator_str
-            );
+            let item_str = match item {
+                FilterItem::Comparison(comparison) => comparison.aql_str(collection_id),
+                FilterItem::Group(filter) if filter.negated => filter.aql_str(collection_id),
+                FilterItem::Group(filter) => format!("({})", filter.aql_str(collection_id)),
+            };
+            res = format!("{} {}{}", res, item_str, operator_str);
+        }
+        let res = res.trim_start();
+        if self.negated {
+            format!("NOT ({})", res)
+        } else {
+            String::from(res)
+        }
+    }
+
+    /// Checks if the `document` matches the filter without querying the database, following the
+    /// AQL operator precedence and comparison semantics (see [`Comparison::evaluate`]).
+    ///
+    /// # Errors
+    ///
+    /// Fails if a comparison can't be evaluated, see [`Comparison::evaluate`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Filter};
+    /// # use serde_json::json;
+    /// let filter = Filter::new(Comparison::field("age").greater_or_equal(18))
+    ///     .and_filter(!Filter::new(Comparison::field("status").in_array(&["banned", "deleted"])));
+    /// assert!(filter.evaluate(&json!({ "age": 31, "status": "active" })).unwrap());
+    /// assert!(!filter.evaluate(&json!({ "age": 12, "status": "active" })).unwrap());
+    /// ```
+    ///
+    /// [`Comparison::evaluate`]: crate::query::Comparison::evaluate
+    pub fn evaluate(&self, document: &Value) -> Result<bool, Error> {
+        self.evaluate_in(document, None)
+    }
+
+    /// Checks if the serialized `record` matches the filter without querying the database, see
+    /// [`evaluate`].
+    /// The `_key`, `_id` and `_rev` fields are available for a [`DatabaseRecord`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if `record` can't be serialized or if a comparison can't be evaluated
+    ///
+    /// [`evaluate`]: Self::evaluate
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    pub fn evaluate_record<T>(&self, record: &T) -> Result<bool, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.evaluate(&serde_json::to_value(record)?)
+    }
+
+    /// Checks if the traversed `vertex` matches the filter without querying the database, the
+    /// path comparisons being evaluated against the serialized traversal `path`, like a
+    /// [`Path`] of a [`TraversalItem`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if a comparison can't be evaluated, see [`Comparison::evaluate`]
+    ///
+    /// [`Path`]: crate::query::Path
+    /// [`TraversalItem`]: crate::query::TraversalItem
+    /// [`Comparison::evaluate`]: crate::query::Comparison::evaluate
+    pub fn evaluate_traversal(&self, vertex: &Value, path: &Value) -> Result<bool, Error> {
+        self.evaluate_in(vertex, Some(path))
+    }
+
+    fn evaluate_in(&self, document: &Value, path: Option<&Value>) -> Result<bool, Error> {
+        // `&&` takes precedence over `||`: the result is a disjunction of conjunctions
+        let mut res = false;
+        let mut conjunction = true;
+        for (i, item) in self.items.iter().enumerate() {
+            let value = match item {
+                FilterItem::Comparison(comparison) => comparison.evaluate_in(document, path)?,
+                FilterItem::Group(filter) => filter.evaluate_in(document, path)?,
+            };
+            if i > 0 && matches!(self.operators.get(i - 1), Some(Operator::Or)) {
+                res = res || conjunction;
+                conjunction = value;
+            } else {
+                conjunction = conjunction && value;
+            }
         }
-        String::from(res.trim_start())
+        Ok((res || conjunction) != self.negated)
+    }
+
+    /// Whether one of the comparisons uses the traversal path variable
+    pub(crate) fn uses_path(&self) -> bool {
+        self.items.iter().any(|item| match item {
+            FilterItem::Comparison(comparison) => comparison.uses_path(),
+            FilterItem::Group(filter) => filter.uses_path(),
+        })
+    }
+
+    /// Registers the values of every comparison in `bind_vars`
+    pub(crate) fn bind_values(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        for item in &mut self.items {
+            match item {
+                FilterItem::Comparison(comparison) => comparison.bind_value(bind_vars),
+                FilterItem::Group(filter) => filter.bind_values(bind_vars),
+            }
+        }
+    }
+
+    /// Takes back the values of every comparison from `bind_vars`
+    pub(crate) fn unbind_values(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        for item in &mut self.items {
+            match item {
+                FilterItem::Comparison(comparison) => comparison.unbind_value(bind_vars),
+                FilterItem::Group(filter) => filter.unbind_values(bind_vars),
+            }
+        }
+    }
+}
+
+impl Not for Filter {
+    type Output = Self;
+
+    /// Negates the whole filter, see [`Filter::negate`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Filter};
+    /// let filter = !Filter::new(Comparison::field("age").greater_than(10))
+    ///     .and(Comparison::field("age").lesser_than(20));
+    /// assert_eq!(filter.aql_str("i"), "NOT (i.age > 10 && i.age < 20)");
+    /// ```
+    fn not(self) -> Self {
+        self.negate()
     }
 }
@@ -1,12 +1,66 @@
 use std::fmt::{self, Display, Formatter};
 
+use serde_json::Value;
+
+/// The traversal direction of a graph query
 #[derive(Copy, Clone, Debug)]
 pub enum GraphQueryDirection {
+    /// Follows the edges from `_from` to `_to` (`OUTBOUND`)
     Outbound,
+    /// Follows the edges from `_to` to `_from` (`INBOUND`)
     Inbound,
+    /// Follows the edges in both directions (`ANY`)
     Any,
 }
 
+/// Uniqueness check applied on the traversed vertices or edges, see [`TraversalOptions`]
+#[derive(Copy, Clone, Debug)]
+pub enum Uniqueness {
+    /// No uniqueness check (`"none"`)
+    None,
+    /// A vertex or edge can't appear twice in the same path (`"path"`)
+    Path,
+    /// A vertex can only be visited once in the whole traversal (`"global"`).
+    /// Only valid for vertices with a breadth-first or weighted traversal order.
+    Global,
+}
+
+/// The traversal order of a graph query, see [`TraversalOptions`]
+#[derive(Copy, Clone, Debug)]
+pub enum TraversalOrder {
+    /// Breadth-first search (`"bfs"`)
+    Bfs,
+    /// Depth-first search (`"dfs"`), the `ArangoDB` default
+    Dfs,
+    /// Lowest weight first (`"weighted"`), see [`TraversalOptions::weighted`]
+    Weighted,
+}
+
+/// Options of a graph traversal, rendered as its `OPTIONS` object.
+/// The `ArangoDB` defaults are kept for every option not set.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::query::{Query, TraversalOptions, TraversalOrder, Uniqueness};
+/// let options = TraversalOptions::new()
+///     .unique_vertices(Uniqueness::Global)
+///     .order(TraversalOrder::Bfs);
+/// let query = Query::outbound(1, 5, "Follows", "User/123").traversal_options(options);
+/// assert_eq!(
+///     query.aql_str(),
+///     r#"FOR a in 1..5 OUTBOUND 'User/123' Follows OPTIONS { uniqueVertices: "global", order: "bfs" } return a"#
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TraversalOptions {
+    unique_vertices: Option<Uniqueness>,
+    unique_edges: Option<Uniqueness>,
+    order: Option<TraversalOrder>,
+    weight: Option<(String, f64)>,
+    edge_collections: Vec<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct GraphQueryData {
     pub direction: GraphQueryDirection,
@@ -29,3 +83,139 @@ impl Display for GraphQueryDirection {
         )
     }
 }
+
+impl Display for Uniqueness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::None => "none",
+                Self::Path => "path",
+                Self::Global => "global",
+            }
+        )
+    }
+}
+
+impl Display for TraversalOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Bfs => "bfs",
+                Self::Dfs => "dfs",
+                Self::Weighted => "weighted",
+            }
+        )
+    }
+}
+
+impl TraversalOptions {
+    /// Instantiates new empty options
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `uniqueVertices` option
+    #[inline]
+    #[must_use]
+    pub const fn unique_vertices(mut self, uniqueness: Uniqueness) -> Self {
+        self.unique_vertices = Some(uniqueness);
+        self
+    }
+
+    /// Sets the `uniqueEdges` option, [`Uniqueness::Global`] is not supported for edges
+    #[inline]
+    #[must_use]
+    pub const fn unique_edges(mut self, uniqueness: Uniqueness) -> Self {
+        self.unique_edges = Some(uniqueness);
+        self
+    }
+
+    /// Sets the traversal `order` option
+    #[inline]
+    #[must_use]
+    pub const fn order(mut self, order: TraversalOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Traverses the lowest weight paths first, weighting the edges with their `weight_attribute`
+    /// value. Edges without it weigh `default_weight`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Query, TraversalOptions};
+    /// let query = Query::any(1, 3, "Road", "City/1")
+    ///     .traversal_options(TraversalOptions::new().weighted("distance", 10.0));
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"FOR a in 1..3 ANY 'City/1' Road OPTIONS { order: "weighted", weightAttribute: "distance", defaultWeight: 10.0 } return a"#
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn weighted(mut self, weight_attribute: &str, default_weight: f64) -> Self {
+        self.order = Some(TraversalOrder::Weighted);
+        self.weight = Some((weight_attribute.to_string(), default_weight));
+        self
+    }
+
+    /// Restricts the traversal of a named graph to the given edge collections
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Query, TraversalOptions};
+    /// let query = Query::outbound_graph(1, 2, "SocialGraph", "User/123")
+    ///     .traversal_options(TraversalOptions::new().edge_collections(&["Follows", "Likes"]));
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"FOR a in 1..2 OUTBOUND 'User/123' GRAPH SocialGraph OPTIONS { edgeCollections: ["Follows","Likes"] } return a"#
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn edge_collections(mut self, edge_collections: &[&str]) -> Self {
+        self.edge_collections = edge_collections.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Renders the `OPTIONS` object, or an empty string if no option is set
+    #[must_use]
+    pub fn aql_str(&self) -> String {
+        let mut options = Vec::new();
+        if let Some(uniqueness) = self.unique_vertices {
+            options.push(format!(r#"uniqueVertices: "{}""#, uniqueness));
+        }
+        if let Some(uniqueness) = self.unique_edges {
+            options.push(format!(r#"uniqueEdges: "{}""#, uniqueness));
+        }
+        if let Some(order) = self.order {
+            options.push(format!(r#"order: "{}""#, order));
+        }
+        if let Some((attribute, default_weight)) = &self.weight {
+            options.push(format!(
+                "weightAttribute: {}, defaultWeight: {}",
+                Value::from(attribute.as_str()),
+                Value::from(*default_weight)
+            ));
+        }
+        if !self.edge_collections.is_empty() {
+            options.push(format!(
+                "edgeCollections: {}",
+                Value::from(self.edge_collections.clone())
+            ));
+        }
+        if options.is_empty() {
+            String::new()
+        } else {
+            format!("OPTIONS {{ {} }}", options.join(", "))
+        }
+    }
+}
@@ -1,27 +1,69 @@
 #![allow(clippy::use_self)]
-use crate::query::graph_query::{GraphQueryData, GraphQueryDirection};
+use crate::db::database_service::{
+    count_records, explain_query, profile_query, query_aql_values, query_values,
+    query_values_with_full_count,
+};
+use crate::query::comparison::ComparisonValue;
+use crate::query::graph_query::GraphQueryData;
 use crate::query::operations::{AqlOperation, OperationContainer};
+use crate::query::pagination::PageCursor;
+use crate::query::projection::{traversal_variables, Projection};
 use crate::query::query_id_helper::get_str_identifier;
-use crate::query::utils::{string_from_array, OptionalQueryString};
+use crate::query::utils::{
+    generate_bind_var_name, geo_point, string_from_array, suffix_bind_vars, OptionalQueryString,
+    DOCUMENT_PLACEHOLDER,
+};
 use crate::undefined_record::UndefinedRecord;
-use crate::{DatabaseAccess, Error, Record};
+use crate::{DatabaseAccess, DatabaseRecord, Error, Record};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
 pub use {
-    comparison::Comparison, comparison::ComparisonBuilder, filter::Filter,
-    query_cursor::QueryCursor, query_result::QueryResult,
+    collect::AggregateFunction,
+    collect::Collect,
+    comparison::Comparison,
+    comparison::ComparisonBuilder,
+    field_path::{Field, FieldValue, Fields},
+    filter::Filter,
+    graph_query::{GraphQueryDirection, TraversalOptions, TraversalOrder, Uniqueness},
+    pagination::{Page, Pagination},
+    path_query::Path,
+    path_query::PathQuery,
+    path_query::TraversalItem,
+    query_cursor::QueryCursor,
+    query_options::QueryOptions,
+    query_plan::{
+        PlanCollection, PlanIndex, PlanNode, PlanNodeStats, QueryPlan, QueryProfile, QueryStats,
+        QueryWarning,
+    },
+    query_result::QueryResult,
+    query_spec::{ConditionOperator, ConditionSpec, FilterSpec, QuerySpec, SortSpec},
+    search::ScoringFunction,
+    search::Search,
+    search::SearchClause,
+    search::SearchClauseBuilder,
 };
 
+mod collect;
 mod comparison;
+mod evaluation;
+mod field_path;
 mod filter;
 mod graph_query;
 mod operations;
+mod pagination;
+mod path_query;
+mod projection;
 mod query_cursor;
 mod query_id_helper;
+mod query_options;
+mod query_plan;
 mod query_result;
-mod utils;
+mod query_spec;
+mod search;
+pub(crate) mod utils;
 
 /// Macro to simplify the [`Query`] construction:
 ///
@@ -97,15 +139,28 @@ pub struct Query {
     with_collections: OptionalQueryString,
     collection: String,
     graph_data: Option<GraphQueryData>,
+    traversal_options: Option<TraversalOptions>,
     operations: OperationContainer,
     distinct: bool,
+    projection: Option<Projection>,
+    search: Option<Search>,
     sub_query: Option<String>,
+    raw_aql: Option<String>,
     item_identifier: usize,
+    options: Option<QueryOptions>,
     /// bind parameters to substitute in query string
     pub bind_vars: HashMap<String, Value>,
 }
 
 impl Query {
+    /// Placeholder of the current document in [`let_expr`] expressions and in the filters of
+    /// [`let_subquery`] sub queries, replaced by the document identifier when the query is
+    /// rendered
+    ///
+    /// [`let_expr`]: Self::let_expr
+    /// [`let_subquery`]: Self::let_subquery
+    pub const DOCUMENT: &'static str = DOCUMENT_PLACEHOLDER;
+
     /// Creates a new empty `Query`.
     /// You can call `filter`, `sort`, `limit` and `distinct` to customize the query afterwards
     ///
@@ -126,14 +181,51 @@ impl Query {
             with_collections: OptionalQueryString(None),
             collection: String::from(collection_name),
             graph_data: None,
+            traversal_options: None,
             operations: OperationContainer(vec![]),
             distinct: false,
+            projection: None,
+            search: None,
             sub_query: None,
+            raw_aql: None,
             item_identifier: 0,
+            options: None,
             bind_vars: HashMap::default(),
         }
     }
 
+    /// Creates a `Query` from a raw AQL string, usually through the compile time checked
+    /// [`aql!`] macro. The bind parameters of `aql` must be set with [`bind_var`].
+    ///
+    /// # Note
+    ///
+    /// The raw query can still be customized, its results are then iterated as a sub query.
+    /// It can't be used to update or remove documents.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::raw("FOR u IN User FILTER u.age >= @age RETURN u").bind_var("age", 18);
+    /// assert_eq!(query.aql_str(), "FOR u IN User FILTER u.age >= @age RETURN u");
+    /// let query = query.filter(Comparison::field("active").eq_true().into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in (FOR u IN User FILTER u.age >= @age RETURN u) FILTER a.active == true return a"
+    /// );
+    /// ```
+    ///
+    /// [`aql!`]: crate::aql
+    /// [`bind_var`]: Self::bind_var
+    #[inline]
+    #[must_use]
+    pub fn raw(aql: &str) -> Self {
+        Self {
+            raw_aql: Some(aql.to_string()),
+            ..Self::new(&format!("({})", aql))
+        }
+    }
+
     /// Binds `var` attribute to be substituted by `value` in the query string
     #[must_use]
     #[inline]
@@ -339,26 +431,185 @@ impl Query {
         }
     }
 
+    /// Creates a new shortest path search between `start_vertex` and `target_vertex`.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The traversal direction
+    /// * `start_vertex` - The `_id` of the start vertex
+    /// * `target_vertex` - The `_id` of the target vertex
+    /// * `collection` - The edge collection or the named graph to traverse
+    /// * `named_graph` - Is `collection` a named graph?
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{GraphQueryDirection, Query};
+    /// let query = Query::shortest_path(GraphQueryDirection::Outbound, "City/1", "City/2", "Road", false);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "LET path = (FOR v, e IN OUTBOUND SHORTEST_PATH @aragog_0 TO @aragog_1 Road return { vertex: v, edge: e }) \
+    ///     FILTER LENGTH(path) > 0 \
+    ///     return { vertices: path[*].vertex, edges: path[* FILTER CURRENT.edge != null].edge }"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn shortest_path(
+        direction: GraphQueryDirection,
+        start_vertex: &str,
+        target_vertex: &str,
+        collection: &str,
+        named_graph: bool,
+    ) -> PathQuery {
+        PathQuery::shortest_path(
+            direction,
+            start_vertex,
+            target_vertex,
+            collection,
+            named_graph,
+        )
+    }
+
+    /// Creates a new search of the `k` shortest paths between `start_vertex` and `target_vertex`,
+    /// ordered by weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The traversal direction
+    /// * `start_vertex` - The `_id` of the start vertex
+    /// * `target_vertex` - The `_id` of the target vertex
+    /// * `collection` - The edge collection or the named graph to traverse
+    /// * `named_graph` - Is `collection` a named graph?
+    /// * `k` - The maximum number of returned paths
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{GraphQueryDirection, Query};
+    /// let query = Query::k_shortest_paths(GraphQueryDirection::Any, "City/1", "City/2", "Road", false, 5);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR p IN ANY K_SHORTEST_PATHS @aragog_0 TO @aragog_1 Road LIMIT 5 return p"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn k_shortest_paths(
+        direction: GraphQueryDirection,
+        start_vertex: &str,
+        target_vertex: &str,
+        collection: &str,
+        named_graph: bool,
+        k: u32,
+    ) -> PathQuery {
+        PathQuery::k_shortest_paths(
+            direction,
+            start_vertex,
+            target_vertex,
+            collection,
+            named_graph,
+            k,
+        )
+    }
+
+    /// Creates a new search of all the paths between `start_vertex` and `target_vertex` with a
+    /// length between `min` and `max`.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum path length
+    /// * `max` - The maximum path length
+    /// * `direction` - The traversal direction
+    /// * `start_vertex` - The `_id` of the start vertex
+    /// * `target_vertex` - The `_id` of the target vertex
+    /// * `collection` - The edge collection or the named graph to traverse
+    /// * `named_graph` - Is `collection` a named graph?
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{GraphQueryDirection, Query};
+    /// let query = Query::k_paths(1, 3, GraphQueryDirection::Inbound, "City/1", "City/2", "RoadGraph", true);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR p IN 1..3 INBOUND K_PATHS @aragog_0 TO @aragog_1 GRAPH RoadGraph return p"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn k_paths(
+        min: u16,
+        max: u16,
+        direction: GraphQueryDirection,
+        start_vertex: &str,
+        target_vertex: &str,
+        collection: &str,
+        named_graph: bool,
+    ) -> PathQuery {
+        PathQuery::k_paths(
+            min,
+            max,
+            direction,
+            start_vertex,
+            target_vertex,
+            collection,
+            named_graph,
+        )
+    }
+
     fn join(
-        mut self,
+        self,
         min: u16,
         max: u16,
         mut query: Self,
         direction: GraphQueryDirection,
         named_graph: bool,
     ) -> Self {
-        self.item_identifier = query.item_identifier + 1;
         query.graph_data = Some(GraphQueryData {
             direction,
-            start_vertex: get_str_identifier(self.item_identifier),
+            start_vertex: get_str_identifier(self.outer_identifier(&query)),
             min,
             max,
             named_graph,
         });
-        self.sub_query = Some(query.aql_str());
+        self.nest(query)
+    }
+
+    /// Identifier of the current `Query` once `query` is nested in it. It follows the `query`
+    /// identifier, unless `LET` subqueries already use it.
+    fn outer_identifier(&self, query: &Self) -> usize {
+        self.item_identifier.max(query.item_identifier + 1)
+    }
+
+    /// Nests `query` in the current `Query`, the documents of `query` being returned
+    fn nest(mut self, query: Self) -> Self {
+        self.item_identifier = self.outer_identifier(&query);
+        self.sub_query = Some(query.nested_aql_str(self.item_identifier));
+        self.options = self.options.or(query.options);
+        // The generated bind parameter names of both queries may collide, so ours are renamed
+        self.unbind_values();
+        self.bind_vars.extend(query.bind_vars);
+        self.bind_values();
         self
     }
 
+    /// Registers the values of the search and operations in the query bind parameters
+    fn bind_values(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.bind_values(&mut self.bind_vars);
+        }
+        self.operations.bind_values(&mut self.bind_vars);
+    }
+
+    /// Takes back the values of the search and operations from the query bind parameters
+    fn unbind_values(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.unbind_values(&mut self.bind_vars);
+        }
+        self.operations.unbind_values(&mut self.bind_vars);
+    }
+
     /// Adds an outbound traversing query to the current `Query`.
     ///
     /// # Arguments
@@ -455,6 +706,195 @@ impl Query {
         self.join(min, max, query, GraphQueryDirection::Any, named_graph)
     }
 
+    /// Adds a sub query to the current `Query`, joined on a foreign key: the documents of `query`
+    /// whose `foreign_field` equals the `local_field` of the current documents are returned.
+    /// Call [`return_merged`] or [`return_paired`] on `query` to retrieve the current documents as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The sub query to add
+    /// * `local_field` - The field of the current documents
+    /// * `foreign_field` - The field of the `query` documents referencing `local_field`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::new("Users")
+    ///     .filter(Comparison::field("active").eq_true().into())
+    ///     .join_on(Query::new("Orders").limit(5, None), "_key", "user_id");
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR b in Users FILTER b.active == true FOR a in Orders FILTER a.user_id == b._key LIMIT 5 return a"
+    /// );
+    /// ```
+    ///
+    /// [`return_merged`]: Self::return_merged
+    /// [`return_paired`]: Self::return_paired
+    #[inline]
+    #[must_use]
+    pub fn join_on(self, mut query: Self, local_field: &str, foreign_field: &str) -> Self {
+        let local_field = format!(
+            "{}.{}",
+            get_str_identifier(self.outer_identifier(&query)),
+            local_field
+        );
+        query.operations.0.insert(
+            0,
+            AqlOperation::Filter(
+                Comparison::field(foreign_field)
+                    .equals_statement(&local_field)
+                    .into(),
+            ),
+        );
+        self.nest(query)
+    }
+
+    /// Declares a `LET` variable computed by an AQL `expression`. The variable can be used by the
+    /// following filters (through [`Comparison::statement`]), sorts and projections.
+    ///
+    /// The current document is referenced in `expression` as [`DOCUMENT`] (`$doc`), replaced by
+    /// its identifier when the query is rendered, as the identifier changes when sub queries are
+    /// added.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - The variable name, which must be a valid AQL identifier
+    /// * `expression` - The AQL expression of the variable
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, SortDirection};
+    /// let query = Query::new("Users")
+    ///     .let_expr("full_name", "CONCAT($doc.first_name, ' ', $doc.last_name)")
+    ///     .filter(Comparison::statement("full_name").like("%Doe%").into())
+    ///     .sort("full_name", Some(SortDirection::Asc));
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in Users \
+    ///         LET full_name = CONCAT(a.first_name, ' ', a.last_name) \
+    ///         FILTER full_name LIKE @aragog_0 \
+    ///         SORT full_name ASC \
+    ///         return a"
+    /// );
+    /// ```
+    ///
+    /// [`Comparison::statement`]: crate::query::Comparison::statement
+    /// [`DOCUMENT`]: Self::DOCUMENT
+    #[inline]
+    #[must_use]
+    pub fn let_expr(mut self, variable: &str, expression: &str) -> Self {
+        self.operations.0.push(AqlOperation::Let {
+            variable: variable.to_string(),
+            expression: expression.to_string(),
+        });
+        self
+    }
+
+    /// Declares a `LET` variable holding the array returned by the sub query `query`.
+    /// The variable can be used by the following filters (through [`Comparison::statement`]),
+    /// sorts and projections. Use [`let_subquery_on`] for a sub query correlated to the current
+    /// documents.
+    ///
+    /// # Note
+    ///
+    /// The sub query keeps its own document variables (`a`, `b`...), the current `Query` taking
+    /// the next available identifier. The filters of the sub query can reference the current
+    /// document as [`DOCUMENT`] (`$doc`), replaced by its identifier when the query is rendered.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - The variable name, which must be a valid AQL identifier
+    /// * `query` - The sub query
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::new("Users")
+    ///     .let_subquery("admins", Query::new("Admins").return_expr("a.user_id"))
+    ///     .filter(Comparison::statement("POSITION(admins, b._key)").eq_true().into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR b in Users \
+    ///         LET admins = (FOR a in Admins return a.user_id) \
+    ///         FILTER POSITION(admins, b._key) == true \
+    ///         return b"
+    /// );
+    /// ```
+    ///
+    /// [`Comparison::statement`]: crate::query::Comparison::statement
+    /// [`let_subquery_on`]: Self::let_subquery_on
+    /// [`DOCUMENT`]: Self::DOCUMENT
+    #[inline]
+    #[must_use]
+    pub fn let_subquery(mut self, variable: &str, query: Self) -> Self {
+        self.item_identifier = self.outer_identifier(&query);
+        let aql = query.nested_aql_str(self.item_identifier);
+        // The sub query bind parameters are suffixed with the variable name to avoid collisions
+        let (aql, bind_vars) = suffix_bind_vars(&aql, query.bind_vars, &format!("_{}", variable));
+        self.bind_vars.extend(bind_vars);
+        self.options = self.options.or(query.options);
+        self.operations.0.push(AqlOperation::Let {
+            variable: variable.to_string(),
+            expression: format!("({})", aql),
+        });
+        self
+    }
+
+    /// Declares a `LET` variable holding the documents of the sub query `query` whose
+    /// `foreign_field` equals the `local_field` of the current document, like a [`join_on`]
+    /// grouped in a single array.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - The variable name, which must be a valid AQL identifier
+    /// * `query` - The sub query
+    /// * `local_field` - The field of the current documents
+    /// * `foreign_field` - The field of the `query` documents referencing `local_field`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// // Users with more than 3 orders
+    /// let query = Query::new("Users")
+    ///     .let_subquery_on("orders", Query::new("Orders"), "_key", "user_id")
+    ///     .filter(Comparison::statement("LENGTH(orders)").greater_than(3).into())
+    ///     .return_expr("MERGE(b, { orders })");
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR b in Users \
+    ///         LET orders = (FOR a in Orders FILTER a.user_id == b._key return a) \
+    ///         FILTER LENGTH(orders) > @aragog_0 \
+    ///         return MERGE(b, { orders })"
+    /// );
+    /// ```
+    ///
+    /// [`join_on`]: Self::join_on
+    #[inline]
+    #[must_use]
+    pub fn let_subquery_on(
+        self,
+        variable: &str,
+        mut query: Self,
+        local_field: &str,
+        foreign_field: &str,
+    ) -> Self {
+        // The current document identifier changes if other sub queries are added
+        let local_field = format!("{}.{}", Self::DOCUMENT, local_field);
+        query.operations.0.insert(
+            0,
+            AqlOperation::Filter(
+                Comparison::field(foreign_field)
+                    .equals_statement(&local_field)
+                    .into(),
+            ),
+        );
+        self.let_subquery(variable, query)
+    }
+
     /// Allow the current traversing `Query` to filter the traversed collections and avoid potentian deadlocks.
     ///
     /// # Arguments
@@ -488,7 +928,8 @@ impl Query {
     ///
     /// If you add mutliple `sort` calls it will result in something like `SORT a.field, b.field, c.field`.
     /// If you separate the calls by a `limit` or other operation, the order will be respected and the resulting query
-    /// will look like `SORT a.field LIMIT 10 SORT b.field, c.field`
+    /// will look like `SORT a.field LIMIT 10 SORT b.field, c.field`: the second `SORT` reorders the documents
+    /// entirely, so only its keys define the final order. Use [`sort_by`] to declare every sort key at once.
     ///
     /// # Arguments
     ///
@@ -505,6 +946,8 @@ impl Query {
     ///     .sort("age", Some(SortDirection::Asc)
     /// );
     /// ```
+    ///
+    /// [`sort_by`]: Self::sort_by
     #[inline]
     #[must_use]
     pub fn sort(mut self, field: &str, direction: Option<SortDirection>) -> Self {
@@ -515,153 +958,679 @@ impl Query {
         self
     }
 
-    /// Allows to filter a current `Query` by different comparisons.
+    /// Sorts the current `Query` by multiple fields in a single `SORT` operation, the first field
+    /// having the highest priority and the following ones breaking the ties.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The fields and their sorting direction, by priority
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use aragog::query::{Query, Filter, Comparison};
-    /// let query = Query::new("User").filter(Filter::new(Comparison::field("age").greater_than(18)));
-    /// // or
-    /// let query = Query::new("User").filter(Comparison::field("age").greater_than(18).into());
+    /// # use aragog::query::{Query, SortDirection};
+    /// let query = Query::new("User")
+    ///     .sort_by(&[("last_name", SortDirection::Asc), ("age", SortDirection::Desc)])
+    ///     .limit(10, None);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in User SORT a.last_name ASC, a.age DESC LIMIT 10 return a"
+    /// );
     /// ```
     #[inline]
     #[must_use]
-    pub fn filter(mut self, filter: Filter) -> Self {
-        self.operations.0.push(AqlOperation::Filter(filter));
+    pub fn sort_by(mut self, keys: &[(&str, SortDirection)]) -> Self {
+        self.operations
+            .0
+            .extend(keys.iter().map(|(field, direction)| AqlOperation::Sort {
+                field: (*field).to_string(),
+                direction: direction.clone(),
+            }));
         self
     }
 
-    /// Allows to filter a current `Query` by different comparisons but using the `PRUNE` keyword.
+    /// Sorts the current `Query` by an AQL `expression`, like a function of the document fields.
+    /// Like [`sort`], consecutive calls are merged in a single `SORT` operation.
     ///
     /// # Note
     ///
-    /// The `prune` operation only works for graph queries (See `ArangoDB` documentation)
+    /// The expression is rendered as is, the documents must be referenced by their identifier
+    /// (`a` for a simple query).
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The sorted AQL expression
+    /// * `direction` - Optional sorting direction, `ASC` by default
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use aragog::query::{Query, Filter, Comparison};
-    /// let query = Query::outbound(1, 2, "ChildOf", "User/123").prune(Filter::new(Comparison::field("age").greater_than(18)));
-    /// // or
-    /// let query = Query::outbound(1, 2, "ChildOf", "User/123").prune(Comparison::field("age").greater_than(18).into());
+    /// # use aragog::query::{Query, SortDirection};
+    /// let query = Query::new("Articles")
+    ///     .sort_expr("LENGTH(a.tags)", Some(SortDirection::Desc))
+    ///     .sort("title", None);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in Articles SORT LENGTH(a.tags) DESC, a.title ASC return a"
+    /// );
     /// ```
+    ///
+    /// [`sort`]: Self::sort
     #[inline]
     #[must_use]
-    pub fn prune(mut self, filter: Filter) -> Self {
-        self.operations.0.push(AqlOperation::Prune(filter));
+    pub fn sort_expr(mut self, expression: &str, direction: Option<SortDirection>) -> Self {
+        self.operations.0.push(AqlOperation::ExpressionSort {
+            expression: expression.to_string(),
+            direction: direction.unwrap_or(SortDirection::Asc),
+        });
         self
     }
 
-    /// Allows to paginate a current `Query`.
+    /// Sorts the current `Query` by the distance between `field` and the given point.
+    /// The field must be a `GeoJSON` object or a `[longitude, latitude]` pair.
     ///
     /// # Arguments
     ///
-    /// * `limit` - the maximum returned elements
-    /// * `skip`- optional number of skipped elements
+    /// * `field` - The geo field
+    /// * `latitude` - The latitude of the reference point
+    /// * `longitude` - The longitude of the reference point
+    /// * `direction` - The sorting direction, `ASC` for the nearest documents first
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use aragog::query::Query;
-    /// // We want maximum 10 elements but skip the first 5
-    /// let query = Query::new("User").limit(10, Some(5));
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::new("Stores")
+    ///     .filter(Comparison::field("location").within_distance(48.85, 2.35, 5000.0).into())
+    ///     .sort_by_distance("location", 48.85, 2.35, None)
+    ///     .limit(5, None);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in Stores \
+    ///         FILTER GEO_DISTANCE(a.location, @aragog_1) <= @aragog_0 \
+    ///         SORT GEO_DISTANCE(a.location, @aragog_2) ASC \
+    ///         LIMIT 5 \
+    ///         return a"
+    /// );
     /// ```
     #[inline]
     #[must_use]
-    pub fn limit(mut self, limit: u32, skip: Option<u32>) -> Self {
-        self.operations.0.push(AqlOperation::Limit { skip, limit });
+    pub fn sort_by_distance(
+        mut self,
+        field: &str,
+        latitude: f64,
+        longitude: f64,
+        direction: Option<SortDirection>,
+    ) -> Self {
+        let mut point = ComparisonValue::Value(geo_point(latitude, longitude));
+        point.bind(&mut self.bind_vars);
+        self.operations.0.push(AqlOperation::DistanceSort {
+            field: field.to_string(),
+            point,
+            direction: direction.unwrap_or(SortDirection::Asc),
+        });
         self
     }
 
-    /// Allows to avoid duplicate elements for a `Query`.
+    /// Sorts the results of a view [`search`] by relevance, using the `ArangoSearch` `function`
+    /// score.
     ///
-    /// # Note
+    /// # Arguments
     ///
-    /// If you use sub-queries, only the `distinct` on the last sub query will be used.
+    /// * `function` - The scoring function
+    /// * `direction` - The sorting direction, `DESC` for the most relevant results first
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use aragog::query::{Query, Filter, Comparison};
-    /// let query = Query::new("User")
-    ///     .filter(Filter::new(Comparison::field("age").greater_than(18)))
-    ///     .distinct();
+    /// # use aragog::query::{Query, ScoringFunction, SearchClause, SortDirection};
+    /// let query = Query::new("ProductSearch")
+    ///     .search(SearchClause::field("name").phrase("pizza").into())
+    ///     .sort_by_score(ScoringFunction::Bm25, Some(SortDirection::Desc));
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in ProductSearch SEARCH PHRASE(a.name, @aragog_0) SORT BM25(a) DESC return a"
+    /// );
     /// ```
+    ///
+    /// [`search`]: Self::search
     #[inline]
     #[must_use]
-    pub const fn distinct(mut self) -> Self {
-        self.distinct = true;
+    pub fn sort_by_score(
+        mut self,
+        function: ScoringFunction,
+        direction: Option<SortDirection>,
+    ) -> Self {
+        self.operations.0.push(AqlOperation::ScoreSort {
+            function,
+            direction: direction.unwrap_or(SortDirection::Asc),
+        });
         self
     }
 
-    /// Renders the AQL string corresponding to the current `Query`
+    /// Searches the `ArangoSearch` view targeted by the current `Query` with a `SEARCH` operation.
+    /// The searched values are registered as bind parameters of the query.
+    ///
+    /// # Note
+    ///
+    /// The `Query` must be instantiated with the view name instead of a collection name, the
+    /// documents of every collection linked to the view will be returned.
+    /// Calling `search` again replaces the previous search.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use aragog::query::{Comparison, Query, Filter};
-    /// let mut query = Query::new("User").filter(Filter::new(Comparison::field("age").greater_than(10)).
-    ///     or(Comparison::field("username").in_str_array(&["Felix", "Bianca"]))).distinct();
-    /// assert_eq!(query.to_aql(), String::from("\
-    ///     FOR a in User \
-    ///         FILTER a.age > 10 || a.username IN [\"Felix\", \"Bianca\"] \
-    ///         return DISTINCT a\
-    /// "));
+    /// # use aragog::query::{Query, Search, SearchClause};
+    /// let query = Query::new("ProductSearch").search(
+    ///     Search::new(SearchClause::field("description").phrase("wood fired"))
+    ///         .and(SearchClause::field("price").in_range(5, 15))
+    ///         .analyzer("text_en"),
+    /// );
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in ProductSearch \
+    ///         SEARCH ANALYZER(PHRASE(a.description, @aragog_0) && IN_RANGE(a.price, @aragog_1, @aragog_2, true, true), \"text_en\") \
+    ///         return a"
+    /// );
+    /// assert_eq!(query.bind_vars["aragog_0"], "wood fired");
     /// ```
     #[inline]
     #[must_use]
-    #[deprecated(since = "0.17.0", note = "use `aql_str` instead")]
-    pub fn to_aql(&self) -> String {
-        self.aql_str()
+    pub fn search(mut self, mut search: Search) -> Self {
+        if let Some(mut previous) = self.search.take() {
+            previous.unbind_values(&mut self.bind_vars);
+        }
+        search.bind_values(&mut self.bind_vars);
+        self.search = Some(search);
+        self
     }
 
-    /// Renders the AQL string corresponding to the current `Query`
+    /// Allows to filter a current `Query` by different comparisons.
+    /// The compared values are registered as bind parameters of the query.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use aragog::query::{Comparison, Query, Filter};
-    /// let mut query = Query::new("User").filter(Filter::new(Comparison::field("age").greater_than(10)).
-    ///     or(Comparison::field("username").in_str_array(&["Felix", "Bianca"]))).distinct();
-    /// assert_eq!(query.aql_str(), String::from("\
-    ///     FOR a in User \
-    ///         FILTER a.age > 10 || a.username IN [\"Felix\", \"Bianca\"] \
-    ///         return DISTINCT a\
-    /// "));
+    /// # use aragog::query::{Query, Filter, Comparison};
+    /// let query = Query::new("User").filter(Filter::new(Comparison::field("age").greater_than(18)));
+    /// // or
+    /// let query = Query::new("User").filter(Comparison::field("age").greater_than(18).into());
     /// ```
     #[inline]
     #[must_use]
-    pub fn aql_str(&self) -> String {
-        let collection_id = get_str_identifier(self.item_identifier);
-        let mut res = self.with_collections.to_string();
-        if let Some(graph_data) = &self.graph_data {
-            res = format!(
-                "{}FOR {} in {}..{} {} {} {}{}",
-                res,
-                collection_id,
-                graph_data.min,
-                graph_data.max,
-                graph_data.direction,
-                &graph_data.start_vertex,
-                if graph_data.named_graph { "GRAPH " } else { "" },
-                &self.collection
-            );
-        } else {
-            res = format!("{}FOR {} in {}", res, collection_id, &self.collection);
-        }
-        if !self.operations.0.is_empty() {
-            res = format!("{} {}", res, self.operations.aql_str(&collection_id));
+    pub fn filter(mut self, mut filter: Filter) -> Self {
+        filter.bind_values(&mut self.bind_vars);
+        self.operations.0.push(AqlOperation::Filter(filter));
+        self
+    }
+
+    /// Allows to filter a current `Query` by different comparisons but using the `PRUNE` keyword.
+    ///
+    /// # Note
+    ///
+    /// The `prune` operation only works for graph queries (See `ArangoDB` documentation)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Query, Filter, Comparison};
+    /// let query = Query::outbound(1, 2, "ChildOf", "User/123").prune(Filter::new(Comparison::field("age").greater_than(18)));
+    /// // or
+    /// let query = Query::outbound(1, 2, "ChildOf", "User/123").prune(Comparison::field("age").greater_than(18).into());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn prune(mut self, mut filter: Filter) -> Self {
+        filter.bind_values(&mut self.bind_vars);
+        self.operations.0.push(AqlOperation::Prune(filter));
+        self
+    }
+
+    /// Sets the [`TraversalOptions`] of the current graph `Query`, replacing the previous ones.
+    /// The options of a joined query are kept when it is joined.
+    ///
+    /// # Note
+    ///
+    /// The options are ignored if the `Query` is not a graph traversal
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, TraversalOptions, Uniqueness};
+    /// let query = Query::new("Users").join_outbound(
+    ///     1,
+    ///     3,
+    ///     false,
+    ///     Query::new("Follows")
+    ///         .traversal_options(TraversalOptions::new().unique_vertices(Uniqueness::Path)),
+    /// );
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"FOR b in Users FOR a in 1..3 OUTBOUND b Follows OPTIONS { uniqueVertices: "path" } return a"#
+    /// );
+    /// // `PRUNE` operations are rendered before the options
+    /// let query = Query::outbound(1, 3, "Follows", "User/123")
+    ///     .traversal_options(TraversalOptions::new().unique_vertices(Uniqueness::Path))
+    ///     .prune(Comparison::field("private").eq_true().into())
+    ///     .filter(Comparison::field("age").greater_than(18).into());
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"FOR a in 1..3 OUTBOUND 'User/123' Follows PRUNE a.private == true OPTIONS { uniqueVertices: "path" } FILTER a.age > @aragog_0 return a"#
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn traversal_options(mut self, options: TraversalOptions) -> Self {
+        self.traversal_options = Some(options);
+        self
+    }
+
+    /// Allows to group and aggregate the documents of the current `Query` with a `COLLECT` operation.
+    /// The query will return an object with every variable declared in `collect` instead of the documents,
+    /// so the query should be called with [`call_projection`].
+    ///
+    /// # Note
+    ///
+    /// See [`Collect`] for the restrictions on the following operations. Calling the query fails if
+    /// `collect` declares no variable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Collect, Query, SortDirection};
+    /// let query = Query::new("Users")
+    ///     .collect(Collect::new().group_by("city", "city").count_into("count"))
+    ///     .sort("count", Some(SortDirection::Desc));
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"FOR a in Users COLLECT city = a.city WITH COUNT INTO count SORT count DESC return { "city": city, "count": count }"#
+    /// );
+    /// ```
+    ///
+    /// [`call_projection`]: Self::call_projection
+    #[inline]
+    #[must_use]
+    pub fn collect(mut self, collect: Collect) -> Self {
+        self.operations.0.push(AqlOperation::Collect(collect));
+        self
+    }
+
+    /// Allows to paginate a current `Query`.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - the maximum returned elements
+    /// * `skip`- optional number of skipped elements
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// // We want maximum 10 elements but skip the first 5
+    /// let query = Query::new("User").limit(10, Some(5));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn limit(mut self, limit: u32, skip: Option<u32>) -> Self {
+        self.operations.0.push(AqlOperation::Limit { skip, limit });
+        self
+    }
+
+    /// Returns `count` random documents of the current `Query`, with a `SORT RAND()` operation
+    /// followed by a `LIMIT`. The previous sorts are discarded by the random order.
+    ///
+    /// # Note
+    ///
+    /// Every document is read and shuffled, prefer a filter to reduce the sampled documents on
+    /// large collections.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query};
+    /// let query = Query::new("Questions")
+    ///     .filter(Comparison::field("level").equals(2).into())
+    ///     .sample(5);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in Questions FILTER a.level == @aragog_0 SORT RAND() LIMIT 5 return a"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn sample(mut self, count: u32) -> Self {
+        self.operations.0.push(AqlOperation::Sample(count));
+        self
+    }
+
+    /// Allows to avoid duplicate elements for a `Query`.
+    ///
+    /// # Note
+    ///
+    /// If you use sub-queries, only the `distinct` on the last sub query will be used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Query, Filter, Comparison};
+    /// let query = Query::new("User")
+    ///     .filter(Filter::new(Comparison::field("age").greater_than(18)))
+    ///     .distinct();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Sets the execution options of the `Query`, like a memory limit or a maximum runtime.
+    /// They are applied by every method calling the query.
+    ///
+    /// # Note
+    ///
+    /// When joining queries, the options of the outer query prevail over the options of the
+    /// joined query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, QueryOptions};
+    /// # use std::time::Duration;
+    /// let query = Query::new("Events")
+    ///     .filter(Comparison::field("kind").equals_str("login").into())
+    ///     .options(QueryOptions::new().max_runtime(Duration::from_secs(10)).cache(false));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn options(mut self, options: QueryOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// The execution options of the `Query`, if any
+    pub(crate) const fn query_options(&self) -> Option<&QueryOptions> {
+        self.options.as_ref()
+    }
+
+    /// Allows to return only some fields of the queried documents instead of the whole documents.
+    /// The returned objects have no `_key`, `_id` or `_rev` unless you select them explicitly,
+    /// so the query should be called with [`call_projection`].
+    ///
+    /// # Note
+    ///
+    /// If you use sub-queries, only the projection of the last sub query will be used.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - The names of the returned fields, nested fields can be selected with `.` and
+    ///   are returned in nested objects
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// let query = Query::new("User").select(&["username", "address.city"]);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"FOR a in User return { "username": a.username, "address": { "city": a.address.city } }"#
+    /// );
+    /// ```
+    ///
+    /// [`call_projection`]: Self::call_projection
+    #[inline]
+    #[must_use]
+    pub fn select(mut self, fields: &[&str]) -> Self {
+        self.projection = Some(Projection::Fields(
+            fields.iter().map(ToString::to_string).collect(),
+        ));
+        self
+    }
+
+    /// Allows to replace the returned documents by a custom AQL expression.
+    /// The returned values don't have to be documents, so the query should be called with [`call_projection`].
+    ///
+    /// # Note
+    ///
+    /// The current document is always identified as `a` in the returning query.
+    /// If you use sub-queries, only the projection of the last sub query will be used.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The returned AQL expression
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// let query = Query::new("User").return_expr("CONCAT(a.first_name, ' ', a.last_name)").distinct();
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a in User return DISTINCT CONCAT(a.first_name, ' ', a.last_name)"
+    /// );
+    /// ```
+    ///
+    /// [`call_projection`]: Self::call_projection
+    #[inline]
+    #[must_use]
+    pub fn return_expr(mut self, expression: &str) -> Self {
+        self.projection = Some(Projection::Expression(expression.to_string()));
+        self
+    }
+
+    /// Allows to return the traversed edge and path along with every vertex of a graph `Query`,
+    /// so the query should be called with [`call_traversal`].
+    ///
+    /// # Note
+    ///
+    /// The edge and path of the vertex `a` are declared as `a_edge` and `a_path`, they can be
+    /// used in [`Comparison::statement`] filters.
+    /// If you use sub-queries, call this method on the traversal sub query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// let query = Query::outbound(1, 2, "ChildOf", "User/123").return_traversal();
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR a, a_edge, a_path in 1..2 OUTBOUND 'User/123' ChildOf return [a, a_edge, a_path]"
+    /// );
+    /// ```
+    ///
+    /// [`call_traversal`]: Self::call_traversal
+    /// [`Comparison::statement`]: crate::query::Comparison::statement
+    #[inline]
+    #[must_use]
+    pub fn return_traversal(mut self) -> Self {
+        self.projection = Some(Projection::Traversal);
+        self
+    }
+
+    /// Allows to return the documents of a [`join_on`] sub query merged into the matching document
+    /// of the parent query, the sub query document attributes taking precedence.
+    /// The query should be called with [`call_projection`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// let query = Query::new("Users").join_on(Query::new("Profiles").return_merged(), "_key", "user_id");
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR b in Users FOR a in Profiles FILTER a.user_id == b._key return MERGE(b, a)"
+    /// );
+    /// ```
+    ///
+    /// [`join_on`]: Self::join_on
+    /// [`call_projection`]: Self::call_projection
+    #[inline]
+    #[must_use]
+    pub fn return_merged(mut self) -> Self {
+        self.projection = Some(Projection::Merged);
+        self
+    }
+
+    /// Allows to return the documents of a [`join_on`] sub query paired with the matching document
+    /// of the parent query, as `[parent, document]` arrays.
+    /// The query should be called with [`call_projection`], deserializing tuples of [`DatabaseRecord`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::Query;
+    /// let query = Query::new("Users").join_on(Query::new("Orders").return_paired(), "_key", "user_id");
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR b in Users FOR a in Orders FILTER a.user_id == b._key return [b, a]"
+    /// );
+    /// ```
+    ///
+    /// [`join_on`]: Self::join_on
+    /// [`call_projection`]: Self::call_projection
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    #[inline]
+    #[must_use]
+    pub fn return_paired(mut self) -> Self {
+        self.projection = Some(Projection::Paired);
+        self
+    }
+
+    /// Renders the AQL string corresponding to the current `Query`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    /// let mut query = Query::new("User").filter(Filter::new(Comparison::field("age").greater_than(10)).
+    ///     or(Comparison::field("username").in_str_array(&["Felix", "Bianca"]))).distinct();
+    /// assert_eq!(query.to_aql(), String::from("\
+    ///     FOR a in User \
+    ///         FILTER a.age > @aragog_0 || a.username IN @aragog_1 \
+    ///         return DISTINCT a\
+    /// "));
+    /// ```
+    #[inline]
+    #[must_use]
+    #[deprecated(since = "0.17.0", note = "use `aql_str` instead")]
+    pub fn to_aql(&self) -> String {
+        self.aql_str()
+    }
+
+    /// Renders the AQL string corresponding to the current `Query`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Query, Filter};
+    /// let mut query = Query::new("User").filter(Filter::new(Comparison::field("age").greater_than(10)).
+    ///     or(Comparison::field("username").in_str_array(&["Felix", "Bianca"]))).distinct();
+    /// assert_eq!(query.aql_str(), String::from("\
+    ///     FOR a in User \
+    ///         FILTER a.age > @aragog_0 || a.username IN @aragog_1 \
+    ///         return DISTINCT a\
+    /// "));
+    /// assert_eq!(query.bind_vars["aragog_0"], 10);
+    /// assert_eq!(query.bind_vars["aragog_1"], serde_json::json!(["Felix", "Bianca"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn aql_str(&self) -> String {
+        self.nested_aql_str(self.item_identifier + 1)
+    }
+
+    /// Renders the AQL string of the current `Query`, nested in the query identified by
+    /// `parent_identifier`
+    fn nested_aql_str(&self, parent_identifier: usize) -> String {
+        if let Some(aql) = &self.raw_aql {
+            if self.is_raw() {
+                return aql.clone();
+            }
         }
+        let collection_id = get_str_identifier(self.item_identifier);
+        let mut res = self.body_aql_str();
         if let Some(sub_query) = &self.sub_query {
             res = format!("{} {}", res, sub_query);
         } else {
+            let returned = match (&self.projection, self.operations.last_collect()) {
+                (Some(projection), _) => {
+                    projection.aql_str(self.item_identifier, parent_identifier)
+                }
+                (None, Some(collect)) => collect.return_aql_str(),
+                (None, None) => collection_id,
+            };
             res = format!(
                 "{} return {}{}",
                 res,
                 if self.distinct { "DISTINCT " } else { "" },
-                &collection_id
+                returned
+            );
+        }
+        res
+    }
+
+    /// Returns `true` if the raw AQL of the `Query` was not customized
+    fn is_raw(&self) -> bool {
+        self.raw_aql.is_some()
+            && self.with_collections.0.is_none()
+            && self.operations.0.is_empty()
+            && !self.distinct
+            && self.projection.is_none()
+            && self.search.is_none()
+            && self.sub_query.is_none()
+    }
+
+    /// Renders the `WITH`, `FOR` and operations part of the AQL string, without the `return`
+    fn body_aql_str(&self) -> String {
+        let collection_id = get_str_identifier(self.item_identifier);
+        let mut res = self.with_collections.to_string();
+        let mut operations = self.operations.clone();
+        if let Some(graph_data) = &self.graph_data {
+            let variables = if matches!(self.projection, Some(Projection::Traversal))
+                || self.operations.uses_path()
+            {
+                let (edge, path) = traversal_variables(&collection_id);
+                format!("{}, {}, {}", collection_id, edge, path)
+            } else {
+                collection_id.clone()
+            };
+            res = format!(
+                "{}FOR {} in {}..{} {} {} {}{}",
+                res,
+                variables,
+                graph_data.min,
+                graph_data.max,
+                graph_data.direction,
+                &graph_data.start_vertex,
+                if graph_data.named_graph { "GRAPH " } else { "" },
+                &self.collection
             );
+            let options = self
+                .traversal_options
+                .as_ref()
+                .map(TraversalOptions::aql_str)
+                .unwrap_or_default();
+            if !options.is_empty() {
+                // `PRUNE` must directly follow the traversal, before its `OPTIONS`
+                let (prunes, others) = operations
+                    .0
+                    .into_iter()
+                    .partition(|operation| matches!(operation, AqlOperation::Prune(_)));
+                operations = OperationContainer(others);
+                let prunes = OperationContainer(prunes);
+                if !prunes.0.is_empty() {
+                    res = format!("{} {}", res, prunes.aql_str(&collection_id));
+                }
+                res = format!("{} {}", res, options);
+            }
+        } else {
+            res = format!("{}FOR {} in {}", res, collection_id, &self.collection);
+        }
+        if let Some(search) = &self.search {
+            res = format!("{} SEARCH {}", res, search.aql_str(&collection_id));
+        }
+        if !operations.0.is_empty() {
+            res = format!("{} {}", res, operations.aql_str(&collection_id));
         }
         res
     }
@@ -699,6 +1668,604 @@ impl Query {
         T::get(self, db_accessor).await
     }
 
+    /// Finds all records in database matching the current `Query`, executing it with `options`
+    /// instead of the options set with [`options`], so the same `Query` can run with different
+    /// execution options.
+    ///
+    /// # Note
+    /// Simple wrapper for [`DatabaseRecord`]::[`get_with_options`]
+    ///
+    /// [`options`]: Self::options
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`get_with_options`]: crate::DatabaseRecord::get_with_options
+    #[maybe_async::maybe_async]
+    pub async fn call_with_options<D, T>(
+        &self,
+        db_accessor: &D,
+        options: QueryOptions,
+    ) -> Result<QueryResult<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        T: Record + Send,
+    {
+        DatabaseRecord::get_with_options(self, db_accessor, options).await
+    }
+
+    /// Finds all documents in database matching the current `Query` and deserializes the returned
+    /// values as `P`.
+    /// Use it with [`select`] or [`return_expr`] to retrieve lightweight structs instead of
+    /// whole [`DatabaseRecord`] documents.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query fails or if the returned values can't be deserialized as `P`
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::Query, DatabaseConnection};
+    /// # use serde::Deserialize;
+    /// #
+    /// #[derive(Deserialize)]
+    /// struct UserName {
+    ///     username: String,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let names: Vec<UserName> = Query::new("User")
+    ///     .select(&["username"])
+    ///     .call_projection(&db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`select`]: Self::select
+    /// [`return_expr`]: Self::return_expr
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    #[maybe_async::maybe_async]
+    pub async fn call_projection<D, P>(&self, db_accessor: &D) -> Result<Vec<P>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        P: DeserializeOwned,
+    {
+        query_values(db_accessor, self).await
+    }
+
+    /// Renders the `Query` of a page of the current `Query` documents, returning
+    /// `[document, sort_field value]` pairs. See [`call_page`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the page size is zero, if the pagination token is invalid or if the current `Query`
+    /// is a join, has a sort, a limit, a `COLLECT` operation or a projection.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{Comparison, Pagination, Query, SortDirection};
+    /// let query = Query::new("Users").filter(Comparison::field("active").eq_true().into());
+    /// let page_query = query
+    ///     .paginated(&Pagination::new("created_at", SortDirection::Desc, 20))
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     page_query.aql_str(),
+    ///     "FOR a in Users FILTER a.active == true SORT a.created_at DESC, a._key DESC LIMIT 21 return [a, a.created_at]"
+    /// );
+    /// ```
+    ///
+    /// [`call_page`]: Self::call_page
+    pub fn paginated(&self, pagination: &Pagination) -> Result<Self, Error> {
+        if pagination.page_size == 0 {
+            return Err(Error::ValidationError(String::from(
+                "The page size must be at least 1",
+            )));
+        }
+        if self.sub_query.is_some()
+            || self.projection.is_some()
+            || self.operations.last_collect().is_some()
+            || self.operations.0.iter().any(|operation| {
+                matches!(
+                    operation,
+                    AqlOperation::Limit { .. }
+                        | AqlOperation::Sort { .. }
+                        | AqlOperation::ScoreSort { .. }
+                        | AqlOperation::ExpressionSort { .. }
+                        | AqlOperation::DistanceSort { .. }
+                        | AqlOperation::Sample(_)
+                )
+            })
+        {
+            return Err(Error::ValidationError(format!(
+                "Can't paginate `{}`, the query can't be a join or have a sort, limit, collect or projection",
+                self.aql_str()
+            )));
+        }
+        let field = pagination.sort_field.as_str();
+        let mut query = self.clone();
+        if let Some(cursor) = pagination.cursor()? {
+            let comparator = match pagination.direction {
+                SortDirection::Asc => ">",
+                SortDirection::Desc => "<",
+            };
+            // The `_key` breaks the ties between documents with the same sort value
+            query = query.filter(
+                Filter::new(
+                    Comparison::field(field).compare_value(comparator, cursor.value.clone()),
+                )
+                .or_filter(
+                    Filter::new(Comparison::field(field).compare_value("==", cursor.value)).and(
+                        Comparison::field("_key")
+                            .compare_value(comparator, Value::from(cursor.key)),
+                    ),
+                ),
+            );
+        }
+        let collection_id = get_str_identifier(self.item_identifier);
+        Ok(query
+            .sort(field, Some(pagination.direction.clone()))
+            .sort("_key", Some(pagination.direction.clone()))
+            .limit(pagination.page_size.saturating_add(1), None)
+            .return_expr(&format!("[{}, {}.{}]", collection_id, collection_id, field)))
+    }
+
+    /// Finds a page of the documents matching the current `Query`, with keyset pagination.
+    ///
+    /// The page documents are sorted by the pagination sort field, and the returned [`Page`]
+    /// holds the token of the next page, to give to [`Pagination::after`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query fails or if [`paginated`] fails
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::{Page, Pagination, Query, SortDirection}, DatabaseConnection, Record};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let pagination = Pagination::new("name", SortDirection::Asc, 50).with_total();
+    /// let page: Page<User> = User::query().call_page(&pagination, &db_accessor).await.unwrap();
+    /// if let Some(token) = page.next_token {
+    ///     let pagination = Pagination::new("name", SortDirection::Asc, 50).after(Some(&token));
+    ///     let next_page: Page<User> = User::query().call_page(&pagination, &db_accessor).await.unwrap();
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`paginated`]: Self::paginated
+    #[maybe_async::maybe_async]
+    pub async fn call_page<D, T>(
+        &self,
+        pagination: &Pagination,
+        db_accessor: &D,
+    ) -> Result<Page<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        T: Record + Send,
+    {
+        let position = pagination.cursor()?.map_or(0, |cursor| cursor.position);
+        let query = self.paginated(pagination)?;
+        let (mut items, full_count): (Vec<(DatabaseRecord<T>, Value)>, _) =
+            query_values_with_full_count(
+                db_accessor,
+                &query,
+                pagination.page_size.saturating_add(1),
+                pagination.with_total,
+            )
+            .await?;
+        let page_size = pagination.page_size as usize;
+        let next_token = if items.len() > page_size {
+            items.truncate(page_size);
+            items.last().map(|(document, value)| {
+                PageCursor {
+                    value: value.clone(),
+                    key: document.key().clone(),
+                    position: position + page_size,
+                }
+                .encode()
+            })
+        } else {
+            None
+        };
+        Ok(Page {
+            items: items.into_iter().map(|(document, _)| document).collect(),
+            next_token,
+            total: full_count.map(|count| position + count),
+        })
+    }
+
+    /// Finds all vertices in database matching the current graph `Query`, with the edge leading
+    /// to them and the traversed path.
+    ///
+    /// # Note
+    ///
+    /// The traversal result mode is applied automatically, except for joined queries where
+    /// [`return_traversal`] must be called on the traversal sub query.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query is not a graph traversal, if the query fails or if the vertices or edges
+    /// can't be deserialized as `V` or `E`
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::{Query, TraversalItem}, DatabaseConnection, Record};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// struct Follows {
+    ///     since: u64,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let items: Vec<TraversalItem<User, Follows>> = Query::outbound(1, 2, "Follows", "User/123")
+    ///     .call_traversal(&db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// for (user, _edge, path) in items {
+    ///     println!("{} at depth {}", user.name, path.edges.len());
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`return_traversal`]: Self::return_traversal
+    #[maybe_async::maybe_async]
+    pub async fn call_traversal<D, V, E>(
+        &self,
+        db_accessor: &D,
+    ) -> Result<Vec<TraversalItem<V, E>>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        V: Record + Send,
+        E: Record + Send,
+    {
+        if self.sub_query.is_some() {
+            query_values(db_accessor, self).await
+        } else {
+            query_values(db_accessor, &self.clone().return_traversal()).await
+        }
+    }
+
+    /// Counts the documents in database matching the current `Query`.
+    ///
+    /// # Note
+    ///
+    /// A query on a whole collection is counted with `LENGTH`, a filtered query with
+    /// `COLLECT WITH COUNT INTO`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query fails
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::{Comparison, Query}, DatabaseConnection};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let adults = Query::new("User")
+    ///     .filter(Comparison::field("age").greater_or_equal(18).into())
+    ///     .count(&db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[maybe_async::maybe_async]
+    pub async fn count<D>(&self, db_accessor: &D) -> Result<usize, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        count_records(db_accessor, self).await
+    }
+
+    /// Retrieves the execution plan of the current `Query` chosen by the `ArangoDB` optimizer,
+    /// without running the query.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query is invalid or if the explain request fails
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::{Comparison, Query}, DatabaseConnection};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let plan = Query::new("User")
+    ///     .filter(Comparison::field("email").equals_str("felix@mail.com").into())
+    ///     .explain(&db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// assert!(plan.uses_index("user_email"));
+    /// assert!(plan.full_scans().is_empty());
+    /// # }
+    /// ```
+    #[maybe_async::maybe_async]
+    pub async fn explain<D>(&self, db_accessor: &D) -> Result<QueryPlan, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        explain_query(db_accessor, self).await
+    }
+
+    /// Runs the current `Query` with profiling, retrieving the executed plan with the execution
+    /// statistics and the duration of every query phase. The query results are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query fails
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::{Comparison, Query}, DatabaseConnection};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let profile = Query::new("User")
+    ///     .filter(Comparison::field("age").greater_or_equal(18).into())
+    ///     .profile(&db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// println!(
+    ///     "{} documents scanned in {}s",
+    ///     profile.stats.scanned_full + profile.stats.scanned_index,
+    ///     profile.stats.execution_time
+    /// );
+    /// # }
+    /// ```
+    #[maybe_async::maybe_async]
+    pub async fn profile<D>(&self, db_accessor: &D) -> Result<QueryProfile, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        profile_query(db_accessor, self).await
+    }
+
+    /// Checks the current `Query` can be rendered as valid AQL
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if matches!(self.projection, Some(Projection::Traversal)) && self.graph_data.is_none() {
+            return Err(Error::ValidationError(format!(
+                "Can't return the traversal of `{}`, the query is not a graph traversal",
+                self.collection
+            )));
+        }
+        self.operations.validate()
+    }
+
+    /// Renders the AQL string counting the documents matching the current `Query`
+    #[must_use]
+    pub(crate) fn count_aql_str(&self) -> String {
+        let is_simple = self.sub_query.is_none() && !self.distinct;
+        let is_full_collection = self.graph_data.is_none() && self.search.is_none();
+        if is_simple && is_full_collection && self.operations.0.is_empty() {
+            return format!(
+                "{}RETURN LENGTH({})",
+                self.with_collections.to_string(),
+                self.collection
+            );
+        }
+        if is_simple && self.operations.last_collect().is_none() {
+            return self
+                .clone()
+                .collect(Collect::new().count_into("length"))
+                .return_expr("length")
+                .aql_str();
+        }
+        let query = Self {
+            with_collections: OptionalQueryString(None),
+            ..self.clone()
+        };
+        format!(
+            "{}RETURN LENGTH({})",
+            self.with_collections.to_string(),
+            query.aql_str()
+        )
+    }
+
+    /// Updates all documents in database matching the current `Query` with `patch` in a single
+    /// AQL query, returning the number of updated documents.
+    ///
+    /// # Note
+    ///
+    /// The documents are patched with `UPDATE`, the [`Record`] hooks are not called.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the current `Query` is a graph traversal, a view search, a join, a `COLLECT`, a
+    /// projection or a `DISTINCT` query, if `patch` can't be serialized or if the query fails
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::{Comparison, Query}, DatabaseConnection};
+    /// # use serde_json::json;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let updated_count = Query::new("User")
+    ///     .filter(Comparison::field("last_login").lesser_than(1_600_000_000).into())
+    ///     .update_all(&json!({ "active": false }), &db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[maybe_async::maybe_async]
+    pub async fn update_all<D, P>(&self, patch: &P, db_accessor: &D) -> Result<usize, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        P: Serialize + ?Sized + Sync,
+    {
+        let patch = serde_json::to_value(patch)?;
+        let (aql, bind_vars) = self.modification_aql_str(Some(patch), None)?;
+        let counts: Vec<usize> =
+            query_aql_values(db_accessor, &aql, &bind_vars, self.options.as_ref()).await?;
+        Ok(counts.into_iter().next().unwrap_or_default())
+    }
+
+    /// Updates all documents in database matching the current `Query` with `patch` in a single
+    /// AQL query, returning the updated documents.
+    ///
+    /// # Note
+    ///
+    /// The documents are patched with `UPDATE`, the [`Record`] hooks are not called.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the current `Query` is a graph traversal, a view search, a join, a `COLLECT`, a
+    /// projection or a `DISTINCT` query, if `patch` can't be serialized or if the query fails
+    #[maybe_async::maybe_async]
+    pub async fn update_all_returning<D, T, P>(
+        &self,
+        patch: &P,
+        db_accessor: &D,
+    ) -> Result<QueryResult<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        T: Record + Send,
+        P: Serialize + ?Sized + Sync,
+    {
+        let patch = serde_json::to_value(patch)?;
+        let (aql, bind_vars) = self.modification_aql_str(Some(patch), Some("NEW"))?;
+        let documents: Vec<DatabaseRecord<T>> =
+            query_aql_values(db_accessor, &aql, &bind_vars, self.options.as_ref()).await?;
+        Ok(documents.into())
+    }
+
+    /// Removes all documents in database matching the current `Query` in a single AQL query,
+    /// returning the number of removed documents.
+    ///
+    /// # Note
+    ///
+    /// The [`Record`] hooks are not called.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the current `Query` is a graph traversal, a view search, a join, a `COLLECT`, a
+    /// projection or a `DISTINCT` query or if the query fails
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::{Comparison, Query}, DatabaseConnection};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let removed_count = Query::new("Session")
+    ///     .filter(Comparison::field("expires_at").lesser_than(1_600_000_000).into())
+    ///     .remove_all(&db_accessor)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[maybe_async::maybe_async]
+    pub async fn remove_all<D>(&self, db_accessor: &D) -> Result<usize, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        let (aql, bind_vars) = self.modification_aql_str(None, None)?;
+        let counts: Vec<usize> =
+            query_aql_values(db_accessor, &aql, &bind_vars, self.options.as_ref()).await?;
+        Ok(counts.into_iter().next().unwrap_or_default())
+    }
+
+    /// Removes all documents in database matching the current `Query` in a single AQL query,
+    /// returning the removed documents.
+    ///
+    /// # Note
+    ///
+    /// The [`Record`] hooks are not called.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the current `Query` is a graph traversal, a view search, a join, a `COLLECT`, a
+    /// projection or a `DISTINCT` query or if the query fails
+    #[maybe_async::maybe_async]
+    pub async fn remove_all_returning<D, T>(&self, db_accessor: &D) -> Result<QueryResult<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        T: Record + Send,
+    {
+        let (aql, bind_vars) = self.modification_aql_str(None, Some("OLD"))?;
+        let documents: Vec<DatabaseRecord<T>> =
+            query_aql_values(db_accessor, &aql, &bind_vars, self.options.as_ref()).await?;
+        Ok(documents.into())
+    }
+
+    /// Renders the AQL string updating (with `patch`) or removing the documents matching the
+    /// current `Query`, with its bind parameters.
+    /// Without `returned` value the query returns the number of modified documents.
+    pub(crate) fn modification_aql_str(
+        &self,
+        patch: Option<Value>,
+        returned: Option<&str>,
+    ) -> Result<(String, HashMap<String, Value>), Error> {
+        if self.graph_data.is_some()
+            || self.search.is_some()
+            || self.sub_query.is_some()
+            || self.raw_aql.is_some()
+            || self.distinct
+            || self.projection.is_some()
+            || self.operations.last_collect().is_some()
+        {
+            return Err(Error::ValidationError(format!(
+                "Can't update or remove documents from `{}`, only simple collection queries are allowed",
+                self.aql_str()
+            )));
+        }
+        let collection_id = get_str_identifier(self.item_identifier);
+        let mut bind_vars = self.bind_vars.clone();
+        let operation = patch.map_or_else(
+            || format!("REMOVE {} IN {}", collection_id, self.collection),
+            |patch| {
+                let patch_var = generate_bind_var_name(&bind_vars);
+                let operation = format!(
+                    "UPDATE {} WITH @{} IN {}",
+                    collection_id, patch_var, self.collection
+                );
+                bind_vars.insert(patch_var, patch);
+                operation
+            },
+        );
+        let aql = returned.map_or_else(
+            || {
+                let query = Self {
+                    with_collections: OptionalQueryString(None),
+                    ..self.clone()
+                };
+                format!(
+                    "{}RETURN LENGTH({} {} return 1)",
+                    self.with_collections.to_string(),
+                    query.body_aql_str(),
+                    operation
+                )
+            },
+            |returned| format!("{} {} return {}", self.body_aql_str(), operation, returned),
+        );
+        Ok((aql, bind_vars))
+    }
+
     /// Finds all documents in database matching the current `Query` using batches.
     /// This will return a wrapper for `serde_json`::`Value` as an `UndefinedRecord` inside a cursor.
     ///
@@ -739,6 +2306,29 @@ impl Query {
     {
         T::get_in_batches(self, db_accessor, batch_size).await
     }
+
+    /// Finds all records in database matching the current `Query` using batches, executing it with
+    /// `options` instead of the options set with [`options`].
+    ///
+    /// # Note
+    /// Simple wrapper for [`DatabaseRecord`]::[`get_in_batches_with_options`]
+    ///
+    /// [`options`]: Self::options
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`get_in_batches_with_options`]: crate::DatabaseRecord::get_in_batches_with_options
+    #[maybe_async::maybe_async]
+    pub async fn call_in_batches_with_options<D, T>(
+        &self,
+        db_accessor: &D,
+        batch_size: u32,
+        options: QueryOptions,
+    ) -> Result<QueryCursor<T>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        T: Record + Send,
+    {
+        DatabaseRecord::get_in_batches_with_options(self, db_accessor, batch_size, options).await
+    }
 }
 
 impl Display for Query {
@@ -746,3 +2336,56 @@ impl Display for Query {
         write!(f, "{}", self.aql_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_keeps_the_search() {
+        let query = Query::new("DishSearch").search(Search::new(
+            SearchClause::field("name").starts_with("Pizza"),
+        ));
+        assert_eq!(
+            query.count_aql_str(),
+            "FOR a in DishSearch SEARCH STARTS_WITH(a.name, @aragog_0) COLLECT WITH COUNT INTO length return length"
+        );
+        assert_eq!(Query::new("Dish").count_aql_str(), "RETURN LENGTH(Dish)");
+    }
+
+    #[test]
+    fn empty_collect_is_refused() {
+        let query = Query::new("Users").collect(Collect::new());
+        assert!(matches!(query.validate(), Err(Error::ValidationError(_))));
+        let query = Query::new("Users").collect(Collect::new().into_group("users"));
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn collect_count_with_aggregates_is_refused() {
+        let query = Query::new("Users").collect(
+            Collect::new()
+                .aggregate("oldest", AggregateFunction::Max, "age")
+                .count_into("count"),
+        );
+        assert!(matches!(query.validate(), Err(Error::ValidationError(_))));
+        let query = Query::new("Users").collect(
+            Collect::new()
+                .group_by("age", "age")
+                .into_group("users")
+                .count_into("count"),
+        );
+        assert!(matches!(query.validate(), Err(Error::ValidationError(_))));
+        let query =
+            Query::new("Users").collect(Collect::new().group_by("age", "age").count_into("count"));
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn traversal_of_a_collection_is_refused() {
+        let query = Query::new("Users").return_traversal();
+        assert!(matches!(query.validate(), Err(Error::ValidationError(_))));
+        let query = Query::outbound(1, 2, "Follows", "Users/123").return_traversal();
+        assert!(query.validate().is_ok());
+    }
+}
@@ -1,4 +1,11 @@
-use crate::query::{Filter, SortDirection};
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::query::comparison::ComparisonValue;
+use crate::query::utils::replace_document_placeholder;
+use crate::query::{Collect, Filter, ScoringFunction, SortDirection};
+use crate::Error;
 
 #[derive(Debug, Clone)]
 pub enum AqlOperation {
@@ -12,17 +19,128 @@ pub enum AqlOperation {
         field: String,
         direction: SortDirection,
     },
+    ScoreSort {
+        function: ScoringFunction,
+        direction: SortDirection,
+    },
+    ExpressionSort {
+        expression: String,
+        direction: SortDirection,
+    },
+    DistanceSort {
+        field: String,
+        point: ComparisonValue,
+        direction: SortDirection,
+    },
+    Collect(Collect),
+    Sample(u32),
+    Let {
+        variable: String,
+        expression: String,
+    },
 }
 
 #[derive(Debug, Clone)]
 pub struct OperationContainer(pub Vec<AqlOperation>);
 
+/// The state of the rendered operations the sorted fields depend on
+struct SortContext<'a> {
+    collection_id: &'a str,
+    collected: bool,
+    variables: &'a [&'a str],
+}
+
+impl SortContext<'_> {
+    /// Renders a sorted `field`, which is a document field unless the documents were collected
+    /// or it is a `LET` variable, or one of its attributes
+    fn field(&self, field: &str) -> String {
+        let root = field
+            .split(|c| c == '.' || c == '[')
+            .next()
+            .unwrap_or_default();
+        if self.collected || self.variables.contains(&root) {
+            field.to_string()
+        } else {
+            format!("{}.{}", self.collection_id, field)
+        }
+    }
+}
+
 impl OperationContainer {
+    /// Registers the values of the operations in `bind_vars`
+    pub fn bind_values(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        for operation in &mut self.0 {
+            match operation {
+                AqlOperation::Filter(filter) | AqlOperation::Prune(filter) => {
+                    filter.bind_values(bind_vars);
+                }
+                AqlOperation::DistanceSort { point, .. } => point.bind(bind_vars),
+                _ => (),
+            }
+        }
+    }
+
+    /// Takes back the values of the operations from `bind_vars`
+    pub fn unbind_values(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        for operation in &mut self.0 {
+            match operation {
+                AqlOperation::Filter(filter) | AqlOperation::Prune(filter) => {
+                    filter.unbind_values(bind_vars);
+                }
+                AqlOperation::DistanceSort { point, .. } => point.unbind(bind_vars),
+                _ => (),
+            }
+        }
+    }
+
+    /// Whether a filter or prune operation uses the traversal path variable
+    pub fn uses_path(&self) -> bool {
+        self.0.iter().any(|operation| match operation {
+            AqlOperation::Filter(filter) | AqlOperation::Prune(filter) => filter.uses_path(),
+            _ => false,
+        })
+    }
+
+    /// The last `COLLECT` operation, defining the available variables at the end of the query
+    pub fn last_collect(&self) -> Option<&Collect> {
+        self.0.iter().rev().find_map(|operation| match operation {
+            AqlOperation::Collect(collect) => Some(collect),
+            _ => None,
+        })
+    }
+
+    /// Checks the operations can be rendered as valid AQL
+    pub fn validate(&self) -> Result<(), Error> {
+        for operation in &self.0 {
+            if let AqlOperation::Collect(collect) = operation {
+                collect.validate()?;
+            }
+        }
+        Ok(())
+    }
+
     #[must_use]
     pub fn aql_str(&self, collection_id: &str) -> String {
         let mut res = String::new();
         let mut last_was_sort = false;
+        // After a `COLLECT` the sorts are on the collected variables
+        let mut collected = false;
+        // Sorts on a `LET` variable, or one of its attributes, don't use the document
+        let mut variables: Vec<&str> = Vec::new();
         for operation in &self.0 {
+            let sort_context = SortContext {
+                collection_id,
+                collected,
+                variables: &variables,
+            };
+            if let Some(sort) = Self::sort_aql_str(operation, &sort_context) {
+                // Consecutive sorts are rendered in a single `SORT`
+                res += if last_was_sort { "," } else { " SORT" };
+                res = format!("{} {}", res, sort);
+                last_was_sort = true;
+                continue;
+            }
+            last_was_sort = false;
             match operation {
                 AqlOperation::Limit { skip, limit } => {
                     let skip_str = match skip {
@@ -30,27 +148,76 @@ impl OperationContainer {
                         Some(val) => format!("{}, ", val),
                     };
                     res = format!("{} LIMIT {}{}", res, skip_str, limit);
-                    last_was_sort = false;
                 }
                 AqlOperation::Filter(filter) => {
                     res = format!("{} FILTER {}", res, filter.aql_str(collection_id));
-                    last_was_sort = false;
                 }
                 AqlOperation::Prune(filter) => {
                     res = format!("{} PRUNE {}", res, filter.aql_str(collection_id));
-                    last_was_sort = false;
                 }
-                AqlOperation::Sort { field, direction } => {
-                    if last_was_sort {
-                        res += ",";
-                    } else {
-                        res += " SORT";
-                    }
-                    res = format!("{} {}.{} {}", res, collection_id, field, direction);
-                    last_was_sort = true;
+                AqlOperation::Collect(collect) => {
+                    res = format!("{} {}", res, collect.aql_str(collection_id));
+                    collected = true;
+                }
+                AqlOperation::Sample(count) => {
+                    res = format!("{} SORT RAND() LIMIT {}", res, count);
+                }
+                AqlOperation::Let {
+                    variable,
+                    expression,
+                } => {
+                    res = format!(
+                        "{} {}",
+                        res,
+                        Self::let_aql_str(variable, expression, collection_id)
+                    );
+                    variables.push(variable);
                 }
+                AqlOperation::Sort { .. }
+                | AqlOperation::ScoreSort { .. }
+                | AqlOperation::ExpressionSort { .. }
+                | AqlOperation::DistanceSort { .. } => (),
             }
         }
         String::from(res.trim_start())
     }
+
+    /// Renders the sort item (`expression DIRECTION`) of `operation`, or `None` if it is not a sort
+    fn sort_aql_str(operation: &AqlOperation, context: &SortContext) -> Option<String> {
+        let sort = match operation {
+            AqlOperation::Sort { field, direction } => {
+                format!("{} {}", context.field(field), direction)
+            }
+            AqlOperation::ScoreSort {
+                function,
+                direction,
+            } => format!("{}({}) {}", function, context.collection_id, direction),
+            AqlOperation::ExpressionSort {
+                expression,
+                direction,
+            } => format!("{} {}", expression, direction),
+            AqlOperation::DistanceSort {
+                field,
+                point,
+                direction,
+            } => format!(
+                "GEO_DISTANCE({}, {}) {}",
+                context.field(field),
+                point,
+                direction
+            ),
+            _ => return None,
+        };
+        Some(sort)
+    }
+
+    /// Renders the `LET` statement of `variable`, the placeholder of the current document being
+    /// replaced by `collection_id`
+    fn let_aql_str(variable: &str, expression: &str, collection_id: &str) -> String {
+        format!(
+            "LET {} = {}",
+            variable,
+            replace_document_placeholder(expression, collection_id)
+        )
+    }
 }
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write;
+
+use crate::query::SortDirection;
+use crate::{DatabaseRecord, Error};
+
+/// Keyset pagination parameters of a [`Query`], see [`Query::call_page`].
+///
+/// The documents are sorted by the `sort_field` (then by `_key` to break ties) and every page
+/// starts right after the last document of the previous page, identified by an opaque token.
+/// Unlike `LIMIT` offsets, the cost of a page doesn't grow with its position.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::query::{Pagination, SortDirection};
+/// // First page
+/// let pagination = Pagination::new("created_at", SortDirection::Desc, 20).with_total();
+/// // Following pages, with the `next_token` of the previous `Page`
+/// # let next_token = None;
+/// let pagination = Pagination::new("created_at", SortDirection::Desc, 20).after(next_token);
+/// ```
+///
+/// [`Query`]: crate::query::Query
+/// [`Query::call_page`]: crate::query::Query::call_page
+#[derive(Clone, Debug)]
+pub struct Pagination {
+    pub(crate) sort_field: String,
+    pub(crate) direction: SortDirection,
+    pub(crate) page_size: u32,
+    pub(crate) token: Option<String>,
+    pub(crate) with_total: bool,
+}
+
+/// A page of documents, as returned by [`Query::call_page`]
+///
+/// [`Query::call_page`]: crate::query::Query::call_page
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// The documents of the page
+    pub items: Vec<DatabaseRecord<T>>,
+    /// The token of the next page, `None` on the last page
+    pub next_token: Option<String>,
+    /// The total number of matching documents, only provided with [`Pagination::with_total`]
+    pub total: Option<usize>,
+}
+
+/// Position of a page, encoded in the page tokens
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PageCursor {
+    /// `sort_field` value of the last document of the previous page
+    #[serde(rename = "v")]
+    pub value: Value,
+    /// `_key` of the last document of the previous page
+    #[serde(rename = "k")]
+    pub key: String,
+    /// Number of documents in the previous pages
+    #[serde(rename = "p")]
+    pub position: usize,
+}
+
+impl Pagination {
+    /// Instantiates the pagination of the first page of `page_size` documents sorted by `sort_field`
+    #[inline]
+    #[must_use]
+    pub fn new(sort_field: &str, direction: SortDirection, page_size: u32) -> Self {
+        Self {
+            sort_field: sort_field.to_string(),
+            direction,
+            page_size,
+            token: None,
+            with_total: false,
+        }
+    }
+
+    /// Starts the page after the page with the given `next_token`, or at the first page with `None`.
+    /// The token must come from a page with the same query, sort field and direction.
+    #[inline]
+    #[must_use]
+    pub fn after(mut self, token: Option<&str>) -> Self {
+        self.token = token.map(ToString::to_string);
+        self
+    }
+
+    /// Requests the total number of matching documents, taken from the `fullCount` query statistic
+    #[inline]
+    #[must_use]
+    pub const fn with_total(mut self) -> Self {
+        self.with_total = true;
+        self
+    }
+
+    /// Decodes the token of the page, if any
+    pub(crate) fn cursor(&self) -> Result<Option<PageCursor>, Error> {
+        self.token.as_deref().map(PageCursor::decode).transpose()
+    }
+}
+
+impl PageCursor {
+    /// Encodes the cursor as an opaque hexadecimal token
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        let mut token = String::with_capacity(bytes.len() * 2);
+        for byte in bytes {
+            // Writing to a `String` can't fail
+            let _ = write!(token, "{:02x}", byte);
+        }
+        token
+    }
+
+    /// Decodes a token rendered by [`encode`]
+    ///
+    /// [`encode`]: Self::encode
+    pub fn decode(token: &str) -> Result<Self, Error> {
+        let invalid_token = || Error::ValidationError(format!("Invalid page token `{}`", token));
+        let bytes: Vec<u8> = (0..token.len())
+            .step_by(2)
+            .map(|i| {
+                token
+                    .get(i..i + 2)
+                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(invalid_token)?;
+        serde_json::from_slice(&bytes).map_err(|_| invalid_token())
+    }
+}
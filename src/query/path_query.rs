@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::db::database_service::query_aql_values;
+use crate::query::comparison::ComparisonValue;
+use crate::query::GraphQueryDirection;
+use crate::{DatabaseAccess, DatabaseRecord, EdgeRecord, Error, Record};
+
+/// A graph path, as returned by a [`PathQuery`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Path<V, E> {
+    /// The path vertices, from the start vertex to the target vertex
+    pub vertices: Vec<DatabaseRecord<V>>,
+    /// The path edges, `edges[i]` links `vertices[i]` and `vertices[i + 1]`
+    pub edges: Vec<DatabaseRecord<EdgeRecord<E>>>,
+    /// The path weight, only provided by [`Query::k_shortest_paths`]
+    ///
+    /// [`Query::k_shortest_paths`]: crate::query::Query::k_shortest_paths
+    #[serde(default)]
+    pub weight: Option<f64>,
+}
+
+/// A traversed vertex with the edge leading to it and the path from the start vertex, as
+/// returned by [`Query::call_traversal`].
+/// The edge is `None` for the start vertex of a traversal with a minimum depth of `0`.
+///
+/// [`Query::call_traversal`]: crate::query::Query::call_traversal
+pub type TraversalItem<V, E> = (
+    DatabaseRecord<V>,
+    Option<DatabaseRecord<EdgeRecord<E>>>,
+    Path<V, E>,
+);
+
+#[derive(Clone, Debug)]
+enum PathQueryKind {
+    ShortestPath,
+    KShortestPaths { limit: u32 },
+    KPaths { min: u16, max: u16 },
+}
+
+/// A path search between two vertices, built with [`Query::shortest_path`],
+/// [`Query::k_shortest_paths`] or [`Query::k_paths`].
+///
+/// # Note
+///
+/// The start and target vertices are registered as bind parameters of the query.
+///
+/// [`Query::shortest_path`]: crate::query::Query::shortest_path
+/// [`Query::k_shortest_paths`]: crate::query::Query::k_shortest_paths
+/// [`Query::k_paths`]: crate::query::Query::k_paths
+#[derive(Clone, Debug)]
+pub struct PathQuery {
+    kind: PathQueryKind,
+    direction: GraphQueryDirection,
+    start_vertex: ComparisonValue,
+    target_vertex: ComparisonValue,
+    collection: String,
+    named_graph: bool,
+    weight: Option<(String, f64)>,
+    /// bind parameters to substitute in query string
+    pub bind_vars: HashMap<String, Value>,
+}
+
+impl PathQuery {
+    fn new(
+        kind: PathQueryKind,
+        direction: GraphQueryDirection,
+        start_vertex: &str,
+        target_vertex: &str,
+        collection: &str,
+        named_graph: bool,
+    ) -> Self {
+        let mut bind_vars = HashMap::new();
+        let mut start_vertex = ComparisonValue::Value(Value::from(start_vertex));
+        start_vertex.bind(&mut bind_vars);
+        let mut target_vertex = ComparisonValue::Value(Value::from(target_vertex));
+        target_vertex.bind(&mut bind_vars);
+        Self {
+            kind,
+            direction,
+            start_vertex,
+            target_vertex,
+            collection: collection.to_string(),
+            named_graph,
+            weight: None,
+            bind_vars,
+        }
+    }
+
+    pub(crate) fn shortest_path(
+        direction: GraphQueryDirection,
+        start_vertex: &str,
+        target_vertex: &str,
+        collection: &str,
+        named_graph: bool,
+    ) -> Self {
+        Self::new(
+            PathQueryKind::ShortestPath,
+            direction,
+            start_vertex,
+            target_vertex,
+            collection,
+            named_graph,
+        )
+    }
+
+    pub(crate) fn k_shortest_paths(
+        direction: GraphQueryDirection,
+        start_vertex: &str,
+        target_vertex: &str,
+        collection: &str,
+        named_graph: bool,
+        limit: u32,
+    ) -> Self {
+        Self::new(
+            PathQueryKind::KShortestPaths { limit },
+            direction,
+            start_vertex,
+            target_vertex,
+            collection,
+            named_graph,
+        )
+    }
+
+    pub(crate) fn k_paths(
+        min: u16,
+        max: u16,
+        direction: GraphQueryDirection,
+        start_vertex: &str,
+        target_vertex: &str,
+        collection: &str,
+        named_graph: bool,
+    ) -> Self {
+        Self::new(
+            PathQueryKind::KPaths { min, max },
+            direction,
+            start_vertex,
+            target_vertex,
+            collection,
+            named_graph,
+        )
+    }
+
+    /// Weights the edges with their `weight_attribute` value, edges without it weigh
+    /// `default_weight`. Without weight every edge weighs `1`.
+    ///
+    /// # Note
+    ///
+    /// `K_PATHS` doesn't support weights, the queries built with [`Query::k_paths`] are returned
+    /// unchanged and a warning is logged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{GraphQueryDirection, Query};
+    /// let query = Query::shortest_path(GraphQueryDirection::Any, "City/1", "City/2", "Road", false)
+    ///     .weighted("distance", 10.0);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     r#"LET path = (FOR v, e IN ANY SHORTEST_PATH @aragog_0 TO @aragog_1 Road OPTIONS { weightAttribute: "distance", defaultWeight: 10.0 } return { vertex: v, edge: e }) FILTER LENGTH(path) > 0 return { vertices: path[*].vertex, edges: path[* FILTER CURRENT.edge != null].edge }"#
+    /// );
+    /// ```
+    ///
+    /// [`Query::k_paths`]: crate::query::Query::k_paths
+    #[inline]
+    #[must_use]
+    pub fn weighted(mut self, weight_attribute: &str, default_weight: f64) -> Self {
+        if matches!(self.kind, PathQueryKind::KPaths { .. }) {
+            log::warn!(
+                "K_PATHS queries don't support weights, the `{}` weight is ignored",
+                weight_attribute
+            );
+            return self;
+        }
+        self.weight = Some((weight_attribute.to_string(), default_weight));
+        self
+    }
+
+    fn path_str(&self, keyword: &str) -> String {
+        let mut res = format!(
+            "{} {} {} TO {} {}{}",
+            self.direction,
+            keyword,
+            self.start_vertex,
+            self.target_vertex,
+            if self.named_graph { "GRAPH " } else { "" },
+            self.collection
+        );
+        if let Some((attribute, default_weight)) = &self.weight {
+            res = format!(
+                "{} OPTIONS {{ weightAttribute: {}, defaultWeight: {} }}",
+                res,
+                Value::from(attribute.as_str()),
+                Value::from(*default_weight)
+            );
+        }
+        res
+    }
+
+    /// Renders the AQL string corresponding to the current `PathQuery`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{GraphQueryDirection, Query};
+    /// let query = Query::k_shortest_paths(GraphQueryDirection::Outbound, "City/1", "City/2", "RoadGraph", true, 3);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR p IN OUTBOUND K_SHORTEST_PATHS @aragog_0 TO @aragog_1 GRAPH RoadGraph LIMIT 3 return p"
+    /// );
+    /// let query = Query::k_paths(1, 4, GraphQueryDirection::Outbound, "City/1", "City/2", "Road", false);
+    /// assert_eq!(
+    ///     query.aql_str(),
+    ///     "FOR p IN 1..4 OUTBOUND K_PATHS @aragog_0 TO @aragog_1 Road return p"
+    /// );
+    /// assert_eq!(query.bind_vars["aragog_0"], "City/1");
+    /// ```
+    #[must_use]
+    pub fn aql_str(&self) -> String {
+        match &self.kind {
+            PathQueryKind::ShortestPath => format!(
+                "LET path = (FOR v, e IN {} return {{ vertex: v, edge: e }}) \
+                FILTER LENGTH(path) > 0 \
+                return {{ vertices: path[*].vertex, edges: path[* FILTER CURRENT.edge != null].edge }}",
+                self.path_str("SHORTEST_PATH")
+            ),
+            PathQueryKind::KShortestPaths { limit } => format!(
+                "FOR p IN {} LIMIT {} return p",
+                self.path_str("K_SHORTEST_PATHS"),
+                limit
+            ),
+            PathQueryKind::KPaths { min, max } => format!(
+                "FOR p IN {}..{} {} return p",
+                min,
+                max,
+                self.path_str("K_PATHS")
+            ),
+        }
+    }
+
+    /// Finds the paths in database matching the current `PathQuery`.
+    /// A shortest path search returns at most one path.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query fails or if the path vertices or edges can't be deserialized as `V` or `E`
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::{GraphQueryDirection, Path, Query}, DatabaseConnection, Record};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// struct City {
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// struct Road {
+    ///     distance: f64,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let paths: Vec<Path<City, Road>> =
+    ///     Query::shortest_path(GraphQueryDirection::Any, "City/1", "City/2", "Road", false)
+    ///         .weighted("distance", 1.0)
+    ///         .call(&db_accessor)
+    ///         .await
+    ///         .unwrap();
+    /// # }
+    /// ```
+    #[maybe_async::maybe_async]
+    pub async fn call<D, V, E>(&self, db_accessor: &D) -> Result<Vec<Path<V, E>>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        V: Record + Send,
+        E: Record + Send,
+    {
+        query_aql_values(db_accessor, &self.aql_str(), &self.bind_vars, None).await
+    }
+}
+
+impl Display for PathQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.aql_str())
+    }
+}
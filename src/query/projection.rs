@@ -0,0 +1,83 @@
+use crate::query::query_id_helper::get_str_identifier;
+
+#[derive(Clone, Debug)]
+pub enum Projection {
+    Fields(Vec<String>),
+    Expression(String),
+    /// Traversed vertex, edge and path, see [`traversal_variables`]
+    Traversal,
+    /// Joined document merged into the document of the parent query
+    Merged,
+    /// Document of the parent query and joined document
+    Paired,
+}
+
+/// Names of the edge and path variables of a traversal on `collection_id`
+pub fn traversal_variables(collection_id: &str) -> (String, String) {
+    (
+        format!("{}_edge", collection_id),
+        format!("{}_path", collection_id),
+    )
+}
+
+/// Renders the object of the selected field `paths` of `parent`, nesting the dotted paths in
+/// sub objects. A field selected as a whole takes precedence over its selected sub fields.
+fn object_aql_str(paths: &[Vec<&str>], parent: &str) -> String {
+    let mut keys: Vec<(&str, Vec<Vec<&str>>)> = Vec::new();
+    for path in paths {
+        let (key, sub_path) = match path.split_first() {
+            Some((key, sub_path)) => (*key, sub_path.to_vec()),
+            None => continue,
+        };
+        match keys.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, sub_paths)) => sub_paths.push(sub_path),
+            None => keys.push((key, vec![sub_path])),
+        }
+    }
+    let attributes: Vec<String> = keys
+        .iter()
+        .map(|(key, sub_paths)| {
+            let field = format!("{}.{}", parent, key);
+            let value = if sub_paths.iter().any(Vec::is_empty) {
+                field
+            } else {
+                object_aql_str(sub_paths, &field)
+            };
+            format!(r#""{}": {}"#, key, value)
+        })
+        .collect();
+    format!("{{ {} }}", attributes.join(", "))
+}
+
+impl Projection {
+    /// Renders the returned expression of the query identified by `item_identifier`, nested in
+    /// the query identified by `parent_identifier`
+    #[must_use]
+    pub fn aql_str(&self, item_identifier: usize, parent_identifier: usize) -> String {
+        let collection_id = &get_str_identifier(item_identifier);
+        match self {
+            Self::Fields(fields) => {
+                let paths: Vec<Vec<&str>> = fields
+                    .iter()
+                    .map(|field| field.split('.').collect())
+                    .collect();
+                object_aql_str(&paths, collection_id)
+            }
+            Self::Expression(expression) => expression.clone(),
+            Self::Traversal => {
+                let (edge, path) = traversal_variables(collection_id);
+                format!("[{}, {}, {}]", collection_id, edge, path)
+            }
+            Self::Merged => format!(
+                "MERGE({}, {})",
+                get_str_identifier(parent_identifier),
+                collection_id
+            ),
+            Self::Paired => format!(
+                "[{}, {}]",
+                get_str_identifier(parent_identifier),
+                collection_id
+            ),
+        }
+    }
+}
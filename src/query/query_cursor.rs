@@ -1,7 +1,12 @@
+#[cfg(all(feature = "stream", not(feature = "blocking")))]
+use std::collections::VecDeque;
+
 use arangors_lite::{Cursor, Database};
+#[cfg(all(feature = "stream", not(feature = "blocking")))]
+use futures::stream::{self, BoxStream, StreamExt};
 
 use crate::query::QueryResult;
-use crate::{DatabaseRecord, Record};
+use crate::{DatabaseRecord, Error, Record};
 
 /// Results of AQL query as a cursor in order to batch the communication between server and client.
 ///
@@ -9,6 +14,7 @@ use crate::{DatabaseRecord, Record};
 /// - `next_batch` to move the cursor to the next batch
 /// - `has_more` to check if the current batch is the final one
 /// - `result` to get the query result of the current batch.
+/// - `into_stream` to iterate over the documents as a `futures::Stream` (requires the `stream` feature)
 ///
 /// # Example
 ///
@@ -96,19 +102,138 @@ impl<T: Record> QueryCursor<T> {
         if !self.has_more() {
             return None;
         }
-        if let Some(ref id) = self.cursor.id {
-            self.cursor = match self.database.aql_next_batch(id).await {
-                Ok(cursor) => cursor,
-                Err(error) => {
-                    log::error!("Failed to get next batch: {}", error);
-                    return None;
+        match self.load_next_batch().await {
+            Ok(()) => Some(self.result()),
+            Err(error) => {
+                log::error!("Failed to get next batch: {}", error);
+                None
+            }
+        }
+    }
+
+    /// Moves the cursor to the next batch
+    #[maybe_async::maybe_async]
+    async fn load_next_batch(&mut self) -> Result<(), Error> {
+        let id = self
+            .cursor
+            .id
+            .as_ref()
+            .ok_or_else(|| Error::InternalError {
+                message: Some("No `id` associated to Aql Cursor".to_string()),
+            })?;
+        self.cursor = self.database.aql_next_batch(id).await?;
+        Ok(())
+    }
+
+    /// Converts the cursor into a `futures::Stream` of the queried documents.
+    /// The following batches are fetched when the documents of the current batch are consumed,
+    /// a failure is returned as the last item of the stream.
+    ///
+    /// # Note
+    ///
+    /// Requires the `stream` feature. With the `tokio` feature, dropping the stream before its end
+    /// deletes the server-side cursor from the current tokio runtime. Otherwise the cursor is kept
+    /// on the server until its `ttl` expires, and a warning is logged.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{DatabaseConnection, Record};
+    /// # use futures::StreamExt;
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// # #[derive(Record, Clone, Serialize, Deserialize)]
+    /// # struct User {
+    /// #    username: String,
+    /// # }
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let cursor = User::get_in_batches(&User::query(), &db_accessor, 100).await.unwrap();
+    /// let mut users = cursor.into_stream();
+    /// while let Some(user) = users.next().await {
+    ///     println!("{}", user.unwrap().username);
+    /// }
+    /// # }
+    /// ```
+    #[cfg(all(feature = "stream", not(feature = "blocking")))]
+    #[must_use]
+    pub fn into_stream(mut self) -> BoxStream<'static, Result<DatabaseRecord<T>, Error>>
+    where
+        T: Send + 'static,
+    {
+        let buffer: VecDeque<DatabaseRecord<T>> = std::mem::take(&mut self.cursor.result).into();
+        stream::unfold(
+            (self, buffer, false),
+            |(mut cursor, mut buffer, failed)| async move {
+                loop {
+                    if let Some(document) = buffer.pop_front() {
+                        return Some((Ok(document), (cursor, buffer, failed)));
+                    }
+                    if failed || !cursor.has_more() {
+                        return None;
+                    }
+                    if let Err(error) = cursor.load_next_batch().await {
+                        // The stream ends after the error
+                        return Some((Err(error), (cursor, buffer, true)));
+                    }
+                    buffer.extend(std::mem::take(&mut cursor.cursor.result));
+                }
+            },
+        )
+        .boxed()
+    }
+}
+
+#[cfg(all(feature = "stream", feature = "tokio", not(feature = "blocking")))]
+impl<T> Drop for QueryCursor<T> {
+    fn drop(&mut self) {
+        if !self.cursor.more {
+            return;
+        }
+        if let Some(id) = self.cursor.id.take() {
+            let database = self.database.clone();
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    handle.spawn(delete_cursor(database, id));
                 }
-            };
-            Some(self.result())
-        } else {
-            log::error!("No `id` associated to Aql Cursor");
-            None
+                Err(_) => log::warn!(
+                    "Can't delete the AQL cursor {} outside of a tokio runtime",
+                    id
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "stream", not(feature = "tokio"), not(feature = "blocking")))]
+impl<T> Drop for QueryCursor<T> {
+    fn drop(&mut self) {
+        if !self.cursor.more {
+            return;
+        }
+        if let Some(id) = &self.cursor.id {
+            log::warn!(
+                "The AQL cursor {} is kept until its ttl expires, enable the `tokio` feature to delete the dropped cursors",
+                id
+            );
+        }
+    }
+}
+
+/// Deletes the server-side cursor `id`, releasing its remaining batches
+#[cfg(all(feature = "stream", feature = "tokio", not(feature = "blocking")))]
+async fn delete_cursor(database: Database, id: String) {
+    let url = match database.url().join(&format!("_api/cursor/{}", id)) {
+        Ok(url) => url,
+        Err(error) => {
+            log::warn!("Failed to delete the AQL cursor {}: {}", id, error);
+            return;
         }
+    };
+    if let Err(error) = database.session().delete(url.to_string(), "").await {
+        log::warn!("Failed to delete the AQL cursor {}: {}", id, error);
     }
 }
 
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+/// Execution options of a [`Query`], attached with [`Query::options`].
+/// Every option left unset keeps the `ArangoDB` server default.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::query::{Query, QueryOptions};
+/// # use std::time::Duration;
+/// let query = Query::new("Events").options(
+///     QueryOptions::new()
+///         .max_runtime(Duration::from_secs(30))
+///         .memory_limit(256 * 1024 * 1024)
+///         .fail_on_warning(true)
+///         .optimizer_rules(&["-all", "+use-indexes"]),
+/// );
+/// // The options don't change the rendered AQL
+/// assert_eq!(query.aql_str(), "FOR a in Events return a");
+/// ```
+///
+/// [`Query`]: crate::query::Query
+/// [`Query::options`]: crate::query::Query::options
+#[derive(Clone, Debug, Default)]
+pub struct QueryOptions {
+    pub(crate) memory_limit: Option<u64>,
+    pub(crate) max_runtime: Option<Duration>,
+    pub(crate) cache: Option<bool>,
+    pub(crate) ttl: Option<u32>,
+    pub(crate) stream: Option<bool>,
+    pub(crate) full_count: Option<bool>,
+    pub(crate) fail_on_warning: Option<bool>,
+    pub(crate) optimizer_rules: Vec<String>,
+}
+
+impl QueryOptions {
+    /// Instantiates empty options, keeping the server defaults
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the memory the query may use to `bytes` (`memoryLimit`), the query fails when it
+    /// goes over the limit
+    #[inline]
+    #[must_use]
+    pub const fn memory_limit(mut self, bytes: u64) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Kills the query when it runs longer than `duration` (`maxRuntime`)
+    #[inline]
+    #[must_use]
+    pub const fn max_runtime(mut self, duration: Duration) -> Self {
+        self.max_runtime = Some(duration);
+        self
+    }
+
+    /// Enables or disables the AQL query results cache (`cache`)
+    #[inline]
+    #[must_use]
+    pub const fn cache(mut self, cache: bool) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Sets the time to live of the server cursor, in seconds (`ttl`).
+    /// Mostly useful for batched queries with slow consumers.
+    #[inline]
+    #[must_use]
+    pub const fn ttl(mut self, seconds: u32) -> Self {
+        self.ttl = Some(seconds);
+        self
+    }
+
+    /// Runs the query as a streaming query (`stream`): the results are computed lazily batch
+    /// after batch instead of being buffered on the server
+    #[inline]
+    #[must_use]
+    pub const fn stream(mut self, stream: bool) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Requests the `fullCount` statistic, the number of documents matched before the last
+    /// `LIMIT` operation
+    #[inline]
+    #[must_use]
+    pub const fn full_count(mut self, full_count: bool) -> Self {
+        self.full_count = Some(full_count);
+        self
+    }
+
+    /// Makes the query fail on warnings instead of returning them (`failOnWarning`)
+    #[inline]
+    #[must_use]
+    pub const fn fail_on_warning(mut self, fail_on_warning: bool) -> Self {
+        self.fail_on_warning = Some(fail_on_warning);
+        self
+    }
+
+    /// Enables (`+rule`) or disables (`-rule`) optimizer rules, `all` matching every rule
+    #[inline]
+    #[must_use]
+    pub fn optimizer_rules(mut self, rules: &[&str]) -> Self {
+        self.optimizer_rules = rules.iter().map(ToString::to_string).collect();
+        self
+    }
+}
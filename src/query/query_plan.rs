@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The execution plan of a [`Query`], as returned by [`Query::explain`]
+///
+/// [`Query`]: crate::query::Query
+/// [`Query::explain`]: crate::query::Query::explain
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlan {
+    /// The plan execution nodes
+    #[serde(default)]
+    pub nodes: Vec<PlanNode>,
+    /// The optimizer rules applied to the plan
+    #[serde(default)]
+    pub rules: Vec<String>,
+    /// The collections used by the query
+    #[serde(default)]
+    pub collections: Vec<PlanCollection>,
+    /// The estimated cost of the query
+    #[serde(default)]
+    pub estimated_cost: f64,
+    /// The estimated number of returned documents
+    #[serde(default)]
+    pub estimated_nr_items: u64,
+    /// The warnings raised by the query
+    #[serde(default)]
+    pub warnings: Vec<QueryWarning>,
+}
+
+/// A node of a [`QueryPlan`], like an `EnumerateCollectionNode` or an `IndexNode`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanNode {
+    /// The node type, like `IndexNode` or `FilterNode`
+    #[serde(rename = "type")]
+    pub node_type: String,
+    /// The node identifier
+    pub id: u64,
+    /// The identifiers of the nodes this node depends on
+    #[serde(default)]
+    pub dependencies: Vec<u64>,
+    /// The estimated cost of the node and its dependencies
+    #[serde(default)]
+    pub estimated_cost: f64,
+    /// The estimated number of documents produced by the node
+    #[serde(default)]
+    pub estimated_nr_items: u64,
+    /// The collection read or written by the node, if any
+    #[serde(default)]
+    pub collection: Option<String>,
+    /// The indexes used by the node, if any
+    #[serde(default)]
+    pub indexes: Vec<PlanIndex>,
+}
+
+/// An index used by a [`PlanNode`]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlanIndex {
+    /// The index identifier
+    pub id: String,
+    /// The index name
+    #[serde(default)]
+    pub name: String,
+    /// The index type, like `primary`, `persistent` or `geo`
+    #[serde(rename = "type")]
+    pub index_type: String,
+    /// The indexed fields
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Whether the index is unique
+    #[serde(default)]
+    pub unique: bool,
+    /// Whether the index is sparse
+    #[serde(default)]
+    pub sparse: bool,
+}
+
+/// A collection used by a [`QueryPlan`]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlanCollection {
+    /// The collection name
+    pub name: String,
+    /// The access type, `read` or `write`
+    #[serde(rename = "type")]
+    pub access_type: String,
+}
+
+/// A warning raised by a query
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct QueryWarning {
+    /// The warning `ArangoDB` error number
+    pub code: u32,
+    /// The warning message
+    pub message: String,
+}
+
+/// The execution profile of a [`Query`], as returned by [`Query::profile`]
+///
+/// [`Query`]: crate::query::Query
+/// [`Query::profile`]: crate::query::Query::profile
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct QueryProfile {
+    /// The executed plan
+    pub plan: QueryPlan,
+    /// The execution statistics
+    pub stats: QueryStats,
+    /// The duration of every query phase (`parsing`, `optimizing plan`, `executing`...), in seconds
+    pub phases: HashMap<String, f64>,
+}
+
+/// The execution statistics of a profiled query
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryStats {
+    /// The number of documents read by full collection scans
+    #[serde(default)]
+    pub scanned_full: u64,
+    /// The number of documents read through indexes
+    #[serde(default)]
+    pub scanned_index: u64,
+    /// The number of documents removed by `FILTER` operations
+    #[serde(default)]
+    pub filtered: u64,
+    /// The number of modified documents
+    #[serde(default)]
+    pub writes_executed: u64,
+    /// The query execution time, in seconds
+    #[serde(default)]
+    pub execution_time: f64,
+    /// The maximum memory used by the query, in bytes
+    #[serde(default)]
+    pub peak_memory_usage: u64,
+    /// The statistics of every plan node
+    #[serde(default)]
+    pub nodes: Vec<PlanNodeStats>,
+}
+
+/// The execution statistics of a [`PlanNode`]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlanNodeStats {
+    /// The node identifier
+    pub id: u64,
+    /// The number of calls to the node
+    #[serde(default)]
+    pub calls: u64,
+    /// The number of documents produced by the node
+    #[serde(default)]
+    pub items: u64,
+    /// The time spent in the node and its dependencies, in seconds
+    #[serde(default)]
+    pub runtime: f64,
+}
+
+impl QueryPlan {
+    /// Iterates over the indexes used by the plan nodes
+    pub fn indexes(&self) -> impl Iterator<Item = &PlanIndex> {
+        self.nodes.iter().flat_map(|node| node.indexes.iter())
+    }
+
+    /// Returns `true` if the plan uses the index called `name`, like the indexes declared in a
+    /// [`DatabaseSchema`]
+    ///
+    /// [`DatabaseSchema`]: crate::schema::DatabaseSchema
+    #[must_use]
+    pub fn uses_index(&self, name: &str) -> bool {
+        self.indexes().any(|index| index.name == name)
+    }
+
+    /// Returns the collections read entirely by the plan, without index
+    #[must_use]
+    pub fn full_scans(&self) -> Vec<&str> {
+        self.nodes
+            .iter()
+            .filter(|node| node.node_type == "EnumerateCollectionNode")
+            .filter_map(|node| node.collection.as_deref())
+            .collect()
+    }
+}
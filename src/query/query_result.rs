@@ -1,5 +1,9 @@
+use crate::db::database_service::query_aql_values;
+use crate::query::utils::suffix_bind_vars;
+use crate::query::Comparison;
 use crate::undefined_record::UndefinedRecord;
-use crate::{DatabaseRecord, Error, Record};
+use crate::{DatabaseAccess, DatabaseRecord, Error, ForeignLink, Link, Record};
+use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 
 /// Query result containing the queried documents
@@ -47,6 +51,175 @@ impl<T: Clone + Record> QueryResult<T> {
     pub fn first_record(self) -> Option<DatabaseRecord<T>> {
         self.0.into_iter().next()
     }
+
+    /// Retrieves the `U` document linked to every document through [`ForeignLink`] in a single
+    /// query, instead of one query per document with [`ForeignLink::linked_model`].
+    /// Documents whose foreign key matches no `U` document are paired with `None`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query fails
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::QueryResult, DatabaseConnection, DatabaseRecord, ForeignLink, Record};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// pub struct Order {
+    ///     pub user_id: String,
+    /// }
+    ///
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// pub struct User {}
+    ///
+    /// impl ForeignLink<User> for Order {
+    ///     fn foreign_key(&self) -> &str {
+    ///         &self.user_id
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let orders: QueryResult<Order> = Order::query().call(&db_accessor).await.unwrap();
+    /// // Two queries instead of one per order
+    /// let orders_with_users: Vec<(DatabaseRecord<Order>, Option<DatabaseRecord<User>>)> =
+    ///     orders.preload(&db_accessor).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`ForeignLink`]: crate::ForeignLink
+    /// [`ForeignLink::linked_model`]: crate::ForeignLink::linked_model
+    #[maybe_async::maybe_async]
+    #[allow(clippy::type_complexity)]
+    pub async fn preload<U, D>(
+        self,
+        db_accessor: &D,
+    ) -> Result<Vec<(DatabaseRecord<T>, Option<DatabaseRecord<U>>)>, Error>
+    where
+        T: ForeignLink<U>,
+        U: Record + Send,
+        D: DatabaseAccess + ?Sized,
+    {
+        let mut keys: Vec<&str> = self
+            .iter()
+            .map(|document| ForeignLink::<U>::foreign_key(&document.record))
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let linked = if keys.is_empty() {
+            QueryResult::new(vec![])
+        } else {
+            let query = U::query().filter(Comparison::field("_key").in_str_array(&keys).into());
+            U::get(&query, db_accessor).await?
+        };
+        let linked: HashMap<String, DatabaseRecord<U>> = linked
+            .0
+            .into_iter()
+            .map(|document| (document.key().clone(), document))
+            .collect();
+        Ok(self
+            .0
+            .into_iter()
+            .map(|document| {
+                let linked_document = linked
+                    .get(ForeignLink::<U>::foreign_key(&document.record))
+                    .cloned();
+                (document, linked_document)
+            })
+            .collect())
+    }
+
+    /// Retrieves the `U` documents linked to every document through [`Link`] in a single query,
+    /// instead of one query per document with [`Link::linked_models`].
+    /// The [`Link::link_query`] of every document is sent as a sub query of a single AQL query, so
+    /// the query grows with the number of documents.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query fails or if it doesn't return one result per document
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// # use aragog::{query::{Comparison, Query, QueryResult}, DatabaseConnection, DatabaseRecord, Link, Record};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// pub struct Order {
+    ///     pub user_id: String,
+    /// }
+    ///
+    /// #[derive(Clone, Serialize, Deserialize, Record)]
+    /// pub struct User {}
+    ///
+    /// impl Link<Order> for DatabaseRecord<User> {
+    ///     fn link_query(&self) -> Query {
+    ///         Order::query().filter(Comparison::field("user_id").equals_str(self.key()).into())
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let db_accessor = DatabaseConnection::builder().build().await.unwrap();
+    /// let users: QueryResult<User> = User::query().call(&db_accessor).await.unwrap();
+    /// let users_with_orders: Vec<(DatabaseRecord<User>, QueryResult<Order>)> =
+    ///     users.preload_links(&db_accessor).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Link`]: crate::Link
+    /// [`Link::linked_models`]: crate::Link::linked_models
+    /// [`Link::link_query`]: crate::Link::link_query
+    #[maybe_async::maybe_async]
+    #[allow(clippy::type_complexity)]
+    pub async fn preload_links<U, D>(
+        self,
+        db_accessor: &D,
+    ) -> Result<Vec<(DatabaseRecord<T>, QueryResult<U>)>, Error>
+    where
+        DatabaseRecord<T>: Link<U>,
+        U: Record + Send,
+        D: DatabaseAccess + ?Sized,
+    {
+        if self.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut bind_vars = HashMap::new();
+        let mut sub_queries = Vec::with_capacity(self.len());
+        for (i, document) in self.iter().enumerate() {
+            let query = Link::<U>::link_query(document);
+            // Every link query generates the same bind parameter names
+            let (aql, query_bind_vars) =
+                suffix_bind_vars(&query.aql_str(), query.bind_vars, &format!("_{}", i));
+            bind_vars.extend(query_bind_vars);
+            sub_queries.push(format!("({})", aql));
+        }
+        let aql = format!("return [{}]", sub_queries.join(", "));
+        let linked =
+            query_aql_values::<Vec<Vec<DatabaseRecord<U>>>, D>(db_accessor, &aql, &bind_vars, None)
+                .await?
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+        if linked.len() != self.len() {
+            return Err(Error::InternalError {
+                message: Some(format!(
+                    "Preloading the links of {} documents returned {} results",
+                    self.len(),
+                    linked.len()
+                )),
+            });
+        }
+        Ok(self
+            .0
+            .into_iter()
+            .zip(linked)
+            .map(|(document, linked_documents)| (document, QueryResult::new(linked_documents)))
+            .collect())
+    }
 }
 
 impl QueryResult<UndefinedRecord> {
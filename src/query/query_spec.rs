@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::query::{Comparison, Filter, Query, SortDirection};
+use crate::Error;
+
+/// A serializable description of the filters, sorts and limit of a [`Query`], meant to be sent
+/// by clients (like a frontend search form) as JSON.
+///
+/// The specification is applied to a server side query with [`apply`], which only accepts the
+/// whitelisted fields. The compared values are registered as query bind parameters, never
+/// rendered in the AQL string.
+///
+/// # Example
+///
+/// ```rust
+/// # use aragog::query::{Query, QuerySpec};
+/// let spec: QuerySpec = serde_json::from_str(r#"{
+///     "filter": {
+///         "and": [
+///             { "field": "age", "op": "gte", "value": 18 },
+///             { "or": [
+///                 { "field": "city", "op": "in", "value": ["Paris", "Lyon"] },
+///                 { "not": { "field": "city", "op": "null" } }
+///             ] }
+///         ]
+///     },
+///     "sort": [{ "field": "age", "direction": "desc" }],
+///     "limit": 20
+/// }"#).unwrap();
+/// let query = spec.apply(Query::new("Users"), &["age", "city"]).unwrap();
+/// assert_eq!(
+///     query.aql_str(),
+///     "FOR a in Users FILTER a.age >= @aragog_0 && (a.city IN @aragog_1 || NOT (a.city == null)) \
+///     SORT a.age DESC LIMIT 20 return a"
+/// );
+/// // Fields out of the whitelist are refused
+/// assert!(spec.apply(Query::new("Users"), &["age"]).is_err());
+/// ```
+///
+/// [`Query`]: crate::query::Query
+/// [`apply`]: Self::apply
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuerySpec {
+    /// The filter of the query
+    #[serde(default)]
+    pub filter: Option<FilterSpec>,
+    /// The sorts of the query, by priority
+    #[serde(default)]
+    pub sort: Vec<SortSpec>,
+    /// The maximum number of returned documents
+    #[serde(default)]
+    pub limit: Option<u32>,
+    /// The number of skipped documents, requires a `limit`
+    #[serde(default)]
+    pub skip: Option<u32>,
+}
+
+/// A serializable filter of a [`QuerySpec`]: a field condition or a group of filters
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum FilterSpec {
+    /// A field condition, like `{ "field": "age", "op": "gte", "value": 18 }`
+    Condition(ConditionSpec),
+    /// Filters which must all match, like `{ "and": [...] }`
+    And {
+        /// The filters
+        and: Vec<FilterSpec>,
+    },
+    /// Filters of which one must match, like `{ "or": [...] }`
+    Or {
+        /// The filters
+        or: Vec<FilterSpec>,
+    },
+    /// A filter which must not match, like `{ "not": {...} }`
+    Not {
+        /// The negated filter
+        not: Box<FilterSpec>,
+    },
+}
+
+/// A field condition of a [`FilterSpec`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConditionSpec {
+    /// The compared field
+    pub field: String,
+    /// The comparison operator
+    pub op: ConditionOperator,
+    /// The compared value, ignored by the `null` and `not_null` operators
+    #[serde(default)]
+    pub value: Value,
+}
+
+/// The operator of a [`ConditionSpec`]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionOperator {
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `>`
+    Gt,
+    /// `>=`
+    Gte,
+    /// `<`
+    Lt,
+    /// `<=`
+    Lte,
+    /// `IN`, the value must be an array
+    In,
+    /// `NOT IN`, the value must be an array
+    NotIn,
+    /// `LIKE`, the value must be a string
+    Like,
+    /// `NOT LIKE`, the value must be a string
+    NotLike,
+    /// `== null`
+    Null,
+    /// `!= null`
+    NotNull,
+}
+
+/// A sort of a [`QuerySpec`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SortSpec {
+    /// The sorted field
+    pub field: String,
+    /// The sort direction, `"asc"` or `"desc"`, ascending by default
+    #[serde(default, with = "direction_format")]
+    pub direction: Option<SortDirection>,
+}
+
+/// Lowercase JSON format of the [`SortSpec`] direction, matching the operators format
+mod direction_format {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::query::SortDirection;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Direction {
+        Asc,
+        Desc,
+    }
+
+    // serde `with` functions receive a reference to the field
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S>(direction: &Option<SortDirection>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let direction = direction.as_ref().map(|direction| match direction {
+            SortDirection::Asc => Direction::Asc,
+            SortDirection::Desc => Direction::Desc,
+        });
+        direction.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<SortDirection>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let direction = Option::<Direction>::deserialize(deserializer)?;
+        Ok(direction.map(|direction| match direction {
+            Direction::Asc => SortDirection::Asc,
+            Direction::Desc => SortDirection::Desc,
+        }))
+    }
+}
+
+/// Checks that `field` is part of the `allowed_fields`
+fn check_field(field: &str, allowed_fields: &[&str]) -> Result<(), Error> {
+    if allowed_fields.contains(&field) {
+        Ok(())
+    } else {
+        Err(Error::ValidationError(format!(
+            "Field `{}` is not allowed",
+            field
+        )))
+    }
+}
+
+impl QuerySpec {
+    /// The maximum `limit` accepted by [`apply`]
+    ///
+    /// [`apply`]: Self::apply
+    pub const DEFAULT_MAX_LIMIT: u32 = 1000;
+
+    /// Applies the filter, sorts and limit of the specification to `query`, only accepting the
+    /// `allowed_fields` and a `limit` up to [`DEFAULT_MAX_LIMIT`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if a filter or a sort uses a field out of `allowed_fields`, if a value doesn't
+    /// match its operator, if a group is empty, if the `limit` is too high or if a `skip` is set
+    /// without `limit`
+    ///
+    /// [`DEFAULT_MAX_LIMIT`]: Self::DEFAULT_MAX_LIMIT
+    pub fn apply(&self, query: Query, allowed_fields: &[&str]) -> Result<Query, Error> {
+        self.apply_with_max_limit(query, allowed_fields, Self::DEFAULT_MAX_LIMIT)
+    }
+
+    /// Applies the filter, sorts and limit of the specification to `query`, only accepting the
+    /// `allowed_fields` and a `limit` up to `max_limit`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a filter or a sort uses a field out of `allowed_fields`, if a value doesn't
+    /// match its operator, if a group is empty, if the `limit` is above `max_limit` or if a
+    /// `skip` is set without `limit`
+    pub fn apply_with_max_limit(
+        &self,
+        mut query: Query,
+        allowed_fields: &[&str],
+        max_limit: u32,
+    ) -> Result<Query, Error> {
+        if let Some(limit) = self.limit.filter(|limit| *limit > max_limit) {
+            return Err(Error::ValidationError(format!(
+                "The limit {} is above the maximum of {}",
+                limit, max_limit
+            )));
+        }
+        if let Some(filter) = &self.filter {
+            query = query.filter(filter.to_filter(allowed_fields)?);
+        }
+        for sort in &self.sort {
+            check_field(&sort.field, allowed_fields)?;
+            query = query.sort(&sort.field, sort.direction.clone());
+        }
+        match (self.limit, self.skip) {
+            (Some(limit), skip) => query = query.limit(limit, skip),
+            (None, Some(_)) => {
+                return Err(Error::ValidationError(
+                    "A `skip` requires a `limit`".to_string(),
+                ))
+            }
+            (None, None) => (),
+        }
+        Ok(query)
+    }
+}
+
+impl FilterSpec {
+    /// Converts the specification into a [`Filter`], only accepting the `allowed_fields`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a condition uses a field out of `allowed_fields`, if a value doesn't match its
+    /// operator or if a group is empty
+    pub fn to_filter(&self, allowed_fields: &[&str]) -> Result<Filter, Error> {
+        match self {
+            Self::Condition(condition) => Ok(Filter::new(condition.to_comparison(allowed_fields)?)),
+            Self::And { and } => Self::combine(and, false, allowed_fields),
+            Self::Or { or } => Self::combine(or, true, allowed_fields),
+            Self::Not { not } => Ok(!not.to_filter(allowed_fields)?),
+        }
+    }
+
+    /// Joins the `filters` with `OR` operators if `or` is set, with `AND` operators otherwise
+    fn combine(filters: &[Self], or: bool, allowed_fields: &[&str]) -> Result<Filter, Error> {
+        let mut res: Option<Filter> = None;
+        for filter in filters {
+            res = Some(match (res, filter) {
+                (None, Self::Condition(condition)) => {
+                    Filter::new(condition.to_comparison(allowed_fields)?)
+                }
+                (None, filter) => Filter::group(filter.to_filter(allowed_fields)?),
+                (Some(res), Self::Condition(condition)) => {
+                    let comparison = condition.to_comparison(allowed_fields)?;
+                    if or {
+                        res.or(comparison)
+                    } else {
+                        res.and(comparison)
+                    }
+                }
+                (Some(res), filter) => {
+                    let filter = filter.to_filter(allowed_fields)?;
+                    if or {
+                        res.or_filter(filter)
+                    } else {
+                        res.and_filter(filter)
+                    }
+                }
+            });
+        }
+        res.ok_or_else(|| Error::ValidationError("Empty filter group".to_string()))
+    }
+}
+
+impl ConditionSpec {
+    /// Converts the condition into a [`Comparison`], only accepting the `allowed_fields`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the field is not part of `allowed_fields` or if the value doesn't match the
+    /// operator
+    pub fn to_comparison(&self, allowed_fields: &[&str]) -> Result<Comparison, Error> {
+        check_field(&self.field, allowed_fields)?;
+        let invalid_value = |expected: &str| {
+            Err(Error::ValidationError(format!(
+                "The `{}` condition value must be {}",
+                self.field, expected
+            )))
+        };
+        let builder = Comparison::field(&self.field);
+        let value = self.value.clone();
+        Ok(match self.op {
+            ConditionOperator::Eq => builder.compare_value("==", value),
+            ConditionOperator::Ne => builder.compare_value("!=", value),
+            ConditionOperator::Gt => builder.compare_value(">", value),
+            ConditionOperator::Gte => builder.compare_value(">=", value),
+            ConditionOperator::Lt => builder.compare_value("<", value),
+            ConditionOperator::Lte => builder.compare_value("<=", value),
+            ConditionOperator::In | ConditionOperator::NotIn if !value.is_array() => {
+                return invalid_value("an array")
+            }
+            ConditionOperator::In => builder.compare_value("IN", value),
+            ConditionOperator::NotIn => builder.compare_value("NOT IN", value),
+            ConditionOperator::Like | ConditionOperator::NotLike if !value.is_string() => {
+                return invalid_value("a string")
+            }
+            ConditionOperator::Like => builder.compare_value("LIKE", value),
+            ConditionOperator::NotLike => builder.compare_value("NOT LIKE", value),
+            ConditionOperator::Null => builder.eq_null(),
+            ConditionOperator::NotNull => builder.not_null(),
+        })
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::query::comparison::ComparisonValue;
+use crate::query::filter::Operator;
+
+/// Macro to simplify the [`SearchClause`] construction:
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate aragog;
+/// # use aragog::query::SearchClause;
+///
+/// # fn main() {
+/// // The following are equivalent:
+/// let clause = SearchClause::field("description").phrase("wood fired");
+/// let clause = search!("description").phrase("wood fired");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! search {
+    ($field_name:expr) => {
+        $crate::query::SearchClause::field($field_name)
+    };
+}
+
+/// Scoring functions of `ArangoSearch` views, used to sort the results of a [`Search`] by relevance
+/// with [`Query::sort_by_score`]
+///
+/// [`Query::sort_by_score`]: crate::query::Query::sort_by_score
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ScoringFunction {
+    /// Okapi BM25 scoring (`BM25`)
+    Bm25,
+    /// Term frequency–inverse document frequency scoring (`TFIDF`)
+    Tfidf,
+}
+
+impl Display for ScoringFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Bm25 => "BM25",
+                Self::Tfidf => "TFIDF",
+            }
+        )
+    }
+}
+
+/// Search operation applied to a view field
+#[derive(Clone, Debug)]
+enum SearchOperation {
+    Equals(ComparisonValue),
+    Phrase(ComparisonValue),
+    StartsWith(ComparisonValue),
+    Tokens {
+        text: ComparisonValue,
+        analyzer: String,
+    },
+    InRange {
+        low: ComparisonValue,
+        high: ComparisonValue,
+    },
+    Exists,
+}
+
+/// Builder for [`SearchClause`]
+#[derive(Clone, Debug)]
+pub struct SearchClauseBuilder {
+    field: String,
+}
+
+/// Struct representing one condition of an `ArangoSearch` `SEARCH` operation.
+///
+/// # Note
+///
+/// Can be built from [`SearchClause::field`] or the `search!` macro and combined in a [`Search`]
+#[derive(Clone, Debug)]
+pub struct SearchClause {
+    field: String,
+    operation: SearchOperation,
+}
+
+impl SearchClauseBuilder {
+    /// Finalizes the current clause with an exact value match.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::SearchClause;
+    /// let clause = SearchClause::field("category").equals("pizza");
+    /// assert_eq!(clause.aql_str("i"), r#"i.category == "pizza""#);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn equals<T>(self, value: T) -> SearchClause
+    where
+        T: Into<Value>,
+    {
+        self.finalize(SearchOperation::Equals(ComparisonValue::Value(
+            value.into(),
+        )))
+    }
+
+    /// Finalizes the current clause with a `PHRASE` match: the field must contain the tokens
+    /// of `phrase` in the same order.
+    ///
+    /// # Note
+    ///
+    /// The phrase is tokenized with the analyzer of the [`Search`], see [`Search::analyzer`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::SearchClause;
+    /// let clause = SearchClause::field("description").phrase("wood fired");
+    /// assert_eq!(clause.aql_str("i"), r#"PHRASE(i.description, "wood fired")"#);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn phrase(self, phrase: &str) -> SearchClause {
+        self.finalize(SearchOperation::Phrase(ComparisonValue::Value(
+            Value::from(phrase),
+        )))
+    }
+
+    /// Finalizes the current clause with a `STARTS_WITH` match.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::SearchClause;
+    /// let clause = SearchClause::field("name").starts_with("marg");
+    /// assert_eq!(clause.aql_str("i"), r#"STARTS_WITH(i.name, "marg")"#);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn starts_with(self, prefix: &str) -> SearchClause {
+        self.finalize(SearchOperation::StartsWith(ComparisonValue::Value(
+            Value::from(prefix),
+        )))
+    }
+
+    /// Finalizes the current clause: the field must contain any token of `text`, tokenized
+    /// with `analyzer`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::SearchClause;
+    /// let clause = SearchClause::field("description").any_token("spicy cheese", "text_en");
+    /// assert_eq!(
+    ///     clause.aql_str("i"),
+    ///     r#"i.description IN TOKENS("spicy cheese", "text_en")"#
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn any_token(self, text: &str, analyzer: &str) -> SearchClause {
+        self.finalize(SearchOperation::Tokens {
+            text: ComparisonValue::Value(Value::from(text)),
+            analyzer: analyzer.to_string(),
+        })
+    }
+
+    /// Finalizes the current clause with an inclusive `IN_RANGE` match.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::SearchClause;
+    /// let clause = SearchClause::field("price").in_range(5, 15);
+    /// assert_eq!(clause.aql_str("i"), "IN_RANGE(i.price, 5, 15, true, true)");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn in_range<T>(self, low: T, high: T) -> SearchClause
+    where
+        T: Into<Value>,
+    {
+        self.finalize(SearchOperation::InRange {
+            low: ComparisonValue::Value(low.into()),
+            high: ComparisonValue::Value(high.into()),
+        })
+    }
+
+    /// Finalizes the current clause: the field must exist in the indexed document.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::SearchClause;
+    /// let clause = SearchClause::field("discount").exists();
+    /// assert_eq!(clause.aql_str("i"), "EXISTS(i.discount)");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn exists(self) -> SearchClause {
+        self.finalize(SearchOperation::Exists)
+    }
+
+    fn finalize(self, operation: SearchOperation) -> SearchClause {
+        SearchClause {
+            field: self.field,
+            operation,
+        }
+    }
+}
+
+impl SearchClause {
+    /// Instantiates a new builder for a `SearchClause` on the view field `field_name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::SearchClause;
+    /// let clause = SearchClause::field("name").starts_with("Pizza");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn field(field_name: &str) -> SearchClauseBuilder {
+        SearchClauseBuilder {
+            field: field_name.to_string(),
+        }
+    }
+
+    /// Simple method to build a [`Search`] from two clauses with a `AND` logic.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{SearchClause, Search};
+    /// let search: Search = SearchClause::field("name")
+    ///     .starts_with("Pizza")
+    ///     .and(SearchClause::field("vegetarian").equals(true));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn and(self, clause: Self) -> Search {
+        Search::new(self).and(clause)
+    }
+
+    /// Simple method to build a [`Search`] from two clauses with a `OR` logic.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{SearchClause, Search};
+    /// let search: Search = SearchClause::field("name")
+    ///     .starts_with("Pizza")
+    ///     .or(SearchClause::field("name").starts_with("Calzone"));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn or(self, clause: Self) -> Search {
+        Search::new(self).or(clause)
+    }
+
+    /// Renders the AQL string corresponding to the current `SearchClause`
+    #[must_use]
+    pub fn aql_str(&self, collection_id: &str) -> String {
+        let field = format!("{}.{}", collection_id, self.field);
+        match &self.operation {
+            SearchOperation::Equals(value) => format!("{} == {}", field, value),
+            SearchOperation::Phrase(phrase) => format!("PHRASE({}, {})", field, phrase),
+            SearchOperation::StartsWith(prefix) => format!("STARTS_WITH({}, {})", field, prefix),
+            SearchOperation::Tokens { text, analyzer } => format!(
+                "{} IN TOKENS({}, {})",
+                field,
+                text,
+                Value::from(analyzer.as_str())
+            ),
+            SearchOperation::InRange { low, high } => {
+                format!("IN_RANGE({}, {}, {}, true, true)", field, low, high)
+            }
+            SearchOperation::Exists => format!("EXISTS({})", field),
+        }
+    }
+
+    fn values_mut(&mut self) -> Vec<&mut ComparisonValue> {
+        match &mut self.operation {
+            SearchOperation::Equals(value)
+            | SearchOperation::Phrase(value)
+            | SearchOperation::StartsWith(value)
+            | SearchOperation::Tokens { text: value, .. } => vec![value],
+            SearchOperation::InRange { low, high } => vec![low, high],
+            SearchOperation::Exists => vec![],
+        }
+    }
+}
+
+/// Allows to search an `ArangoSearch` view according to different [`SearchClause`],
+/// rendered as a `SEARCH` operation by [`Query::search`].
+///
+/// [`Query::search`]: crate::query::Query::search
+#[derive(Clone, Debug)]
+pub struct Search {
+    clauses: Vec<SearchClause>,
+    operators: Vec<Operator>,
+    analyzer: Option<String>,
+}
+
+impl Search {
+    /// Instantiates a new view search from a clause
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{SearchClause, Search};
+    /// let search = Search::new(SearchClause::field("description").phrase("wood fired"));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn new(clause: SearchClause) -> Self {
+        Self {
+            clauses: vec![clause],
+            operators: vec![],
+            analyzer: None,
+        }
+    }
+
+    /// Appends the search current clause(s) with a new one with a `AND` logic.
+    #[must_use]
+    #[inline]
+    pub fn and(mut self, clause: SearchClause) -> Self {
+        self.clauses.push(clause);
+        self.operators.push(Operator::And);
+        self
+    }
+
+    /// Appends the search current clause(s) with a new one with a `OR` logic.
+    #[must_use]
+    #[inline]
+    pub fn or(mut self, clause: SearchClause) -> Self {
+        self.clauses.push(clause);
+        self.operators.push(Operator::Or);
+        self
+    }
+
+    /// Sets the analyzer used by the search clauses, wrapping them in an `ANALYZER` call.
+    /// Without analyzer, `ArangoDB` uses the `identity` analyzer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{SearchClause, Search};
+    /// let search = Search::new(SearchClause::field("description").phrase("wood fired"))
+    ///     .analyzer("text_en");
+    /// assert_eq!(
+    ///     search.aql_str("i"),
+    ///     r#"ANALYZER(PHRASE(i.description, "wood fired"), "text_en")"#
+    /// );
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn analyzer(mut self, analyzer: &str) -> Self {
+        self.analyzer = Some(analyzer.to_string());
+        self
+    }
+
+    /// Renders the AQL string corresponding to the current `Search`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aragog::query::{SearchClause, Search};
+    /// let search = Search::new(SearchClause::field("name").starts_with("Pizza"))
+    ///     .or(SearchClause::field("category").equals("pizza"));
+    /// assert_eq!(
+    ///     search.aql_str("i"),
+    ///     r#"STARTS_WITH(i.name, "Pizza") || i.category == "pizza""#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn aql_str(&self, collection_id: &str) -> String {
+        let mut res = String::new();
+        for (i, clause) in self.clauses.iter().enumerate() {
+            let operator_str = if i >= self.operators.len() {
+                String::new()
+            } else {
+                format!(" {}", self.operators[i])
+            };
+            res = format!("{} {}{}", res, clause.aql_str(collection_id), operator_str);
+        }
+        let res = res.trim_start();
+        self.analyzer.as_ref().map_or_else(
+            || String::from(res),
+            |analyzer| format!("ANALYZER({}, {})", res, Value::from(analyzer.as_str())),
+        )
+    }
+
+    pub(crate) fn bind_values(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        for clause in &mut self.clauses {
+            for value in clause.values_mut() {
+                value.bind(bind_vars);
+            }
+        }
+    }
+
+    pub(crate) fn unbind_values(&mut self, bind_vars: &mut HashMap<String, Value>) {
+        for clause in &mut self.clauses {
+            for value in clause.values_mut() {
+                value.unbind(bind_vars);
+            }
+        }
+    }
+}
+
+impl From<SearchClause> for Search {
+    fn from(clause: SearchClause) -> Self {
+        Self::new(clause)
+    }
+}
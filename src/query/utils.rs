@@ -1,15 +1,15 @@
+use serde_json::Value;
+use std::collections::HashMap;
 use std::fmt::Display;
 
+const BIND_VAR_PREFIX: &str = "aragog_";
+
+/// Placeholder of the current document in `LET` expressions, see `Query::DOCUMENT`
+pub const DOCUMENT_PLACEHOLDER: &str = "$doc";
+
 #[derive(Clone, Debug)]
 pub struct OptionalQueryString(pub Option<String>);
 
-pub fn string_array_from_array<T>(array: &[T]) -> String
-where
-    T: Display,
-{
-    format!("[{}]", string_from_array(array))
-}
-
 pub fn string_from_array<T>(array: &[T]) -> String
 where
     T: Display,
@@ -24,19 +24,82 @@ where
     array_str
 }
 
-pub fn string_array_from_array_str<T>(array: &[T]) -> String
-where
-    T: Display,
-{
-    let mut array_str = String::from("[");
-    for (i, element) in array.iter().enumerate() {
-        array_str = format!(r#"{}"{}""#, array_str, element);
-        if i < array.len() - 1 {
-            array_str += ", ";
+/// Renders a geo point as an AQL `[longitude, latitude]` coordinate pair
+pub fn geo_point(latitude: f64, longitude: f64) -> Value {
+    Value::from(vec![longitude, latitude])
+}
+
+pub fn generate_bind_var_name(bind_vars: &HashMap<String, Value>) -> String {
+    let mut index = bind_vars.len();
+    loop {
+        let name = format!("{}{}", BIND_VAR_PREFIX, index);
+        if !bind_vars.contains_key(&name) {
+            return name;
         }
+        index += 1;
     }
-    array_str += "]";
-    array_str
+}
+
+/// Appends `suffix` to the names of `bind_vars` and to their `@name` or `@@name` references in `aql`,
+/// allowing to combine queries whose generated bind parameter names collide
+pub fn suffix_bind_vars(
+    aql: &str,
+    bind_vars: HashMap<String, Value>,
+    suffix: &str,
+) -> (String, HashMap<String, Value>) {
+    let bytes = aql.as_bytes();
+    let mut res = String::with_capacity(aql.len());
+    let mut last = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'@' {
+            i += 1;
+            continue;
+        }
+        let mut end = i + 1;
+        // Collection bind parameters are referenced as `@@name` and named `@name`
+        if bytes.get(end) == Some(&b'@') {
+            end += 1;
+        }
+        while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_') {
+            end += 1;
+        }
+        if bind_vars.contains_key(&aql[i + 1..end]) {
+            res.push_str(&aql[last..end]);
+            res.push_str(suffix);
+            last = end;
+        }
+        i = end;
+    }
+    res.push_str(&aql[last..]);
+    let bind_vars = bind_vars
+        .into_iter()
+        .map(|(name, value)| (format!("{}{}", name, suffix), value))
+        .collect();
+    (res, bind_vars)
+}
+
+/// Replaces the [`DOCUMENT_PLACEHOLDER`] references in `aql` by `collection_id`, leaving the
+/// longer variable names starting like the placeholder untouched
+pub fn replace_document_placeholder(aql: &str, collection_id: &str) -> String {
+    let mut res = String::with_capacity(aql.len());
+    let mut rest = aql;
+    while let Some(index) = rest.find(DOCUMENT_PLACEHOLDER) {
+        let end = index + DOCUMENT_PLACEHOLDER.len();
+        let is_longer = rest[end..]
+            .chars()
+            .next()
+            .map_or(false, |c| c.is_ascii_alphanumeric() || c == '_');
+        res.push_str(&rest[..index]);
+        res.push_str(if is_longer {
+            DOCUMENT_PLACEHOLDER
+        } else {
+            collection_id
+        });
+        rest = &rest[end..];
+    }
+    res.push_str(rest);
+    res
 }
 
 impl ToString for OptionalQueryString {
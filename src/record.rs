@@ -4,7 +4,7 @@ use serde::Serialize;
 use crate::db::transaction::Transaction;
 use crate::query::{Query, QueryCursor, QueryResult};
 use crate::transaction::TransactionBuilder;
-use crate::{DatabaseAccess, DatabaseConnection, DatabaseRecord, Error};
+use crate::{BulkCreateResult, DatabaseAccess, DatabaseConnection, DatabaseRecord, Error};
 
 /// The main trait of the Aragog library.
 /// Trait for structures that can be stored in Database.
@@ -70,6 +70,45 @@ pub trait Record: DeserializeOwned + Serialize + Clone {
         DatabaseRecord::<Self>::exists(query, db_accessor).await
     }
 
+    /// Counts the documents in database matching a `Query`.
+    /// Simple wrapper for [`Query`]::[`count`]
+    ///
+    /// [`Query`]: crate::query::Query
+    /// [`count`]: crate::query::Query::count
+    async fn count<D>(query: &Query, db_accessor: &D) -> Result<usize, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        query.count(db_accessor).await
+    }
+
+    /// Updates all documents in database matching a `Query` with `patch`, returning the number
+    /// of updated documents.
+    /// Simple wrapper for [`Query`]::[`update_all`]
+    ///
+    /// [`Query`]: crate::query::Query
+    /// [`update_all`]: crate::query::Query::update_all
+    async fn update_all<D, P>(query: &Query, patch: &P, db_accessor: &D) -> Result<usize, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+        P: Serialize + ?Sized + Sync,
+    {
+        query.update_all(patch, db_accessor).await
+    }
+
+    /// Removes all documents in database matching a `Query`, returning the number of removed
+    /// documents.
+    /// Simple wrapper for [`Query`]::[`remove_all`]
+    ///
+    /// [`Query`]: crate::query::Query
+    /// [`remove_all`]: crate::query::Query::remove_all
+    async fn remove_all<D>(query: &Query, db_accessor: &D) -> Result<usize, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        query.remove_all(db_accessor).await
+    }
+
     /// Creates a new document in database.
     /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`create`]
     ///
@@ -108,6 +147,21 @@ pub trait Record: DeserializeOwned + Serialize + Clone {
         DatabaseRecord::create(record, db_accessor).await
     }
 
+    /// Creates multiple documents in database.
+    /// Simple wrapper for [`DatabaseRecord`]<`T`>::[`create_many`]
+    ///
+    /// [`DatabaseRecord`]: crate::DatabaseRecord
+    /// [`create_many`]: crate::DatabaseRecord::create_many
+    async fn create_many<D>(
+        records: Vec<Self>,
+        db_accessor: &D,
+    ) -> Result<BulkCreateResult<Self>, Error>
+    where
+        D: DatabaseAccess + ?Sized,
+    {
+        DatabaseRecord::create_many(records, db_accessor).await
+    }
+
     /// Creates a new `Query` instance for `Self`.
     ///
     /// # Example
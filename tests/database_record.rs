@@ -23,6 +23,122 @@ struct SerializedDishRecord {
     pub price: u16,
 }
 
+mod create_many {
+    use super::*;
+
+    fn dish(name: &str) -> Dish {
+        Dish {
+            name: name.to_string(),
+            description: "Italian Dish".to_string(),
+            price: 10,
+        }
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn creates_all_records() {
+        let connection = common::setup_db().await;
+        let res = DatabaseRecord::create_many(
+            vec![dish("Pizza"), dish("Pasta"), dish("Tiramisu")],
+            &connection,
+        )
+        .await
+        .unwrap();
+        assert!(res.is_success());
+        let names: Vec<&str> = res.created.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["Pizza", "Pasta", "Tiramisu"]);
+        let found: DatabaseRecord<Dish> = DatabaseRecord::find(res.created[1].key(), &connection)
+            .await
+            .unwrap();
+        assert_eq!(found.name, "Pasta");
+        let count = Dish::count(&Dish::query(), &connection).await.unwrap();
+        assert_eq!(count, 3);
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn reports_rejected_records() {
+        let connection = common::setup_db().await;
+        let res = Dish::create_many(
+            vec![dish("Pizza"), dish("Pasta"), dish("Pizza")],
+            &connection,
+        )
+        .await
+        .unwrap();
+        assert!(!res.is_success());
+        assert_eq!(res.created.len(), 2);
+        assert_eq!(res.failed.len(), 1);
+        let (index, error) = &res.failed[0];
+        assert_eq!(*index, 2);
+        assert_eq!(error.http_code(), 409);
+        let count = Dish::count(&Dish::query(), &connection).await.unwrap();
+        assert_eq!(count, 2);
+    }
+}
+
+mod upsert {
+    use super::*;
+    use aragog::query::Comparison;
+
+    fn pizza(price: u16) -> Dish {
+        Dish {
+            name: "Pizza".to_string(),
+            description: "Italian Dish".to_string(),
+            price,
+        }
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn creates_then_replaces() {
+        let connection = common::setup_db().await;
+        let search = Comparison::field("name").equals_str("Pizza");
+        let created =
+            DatabaseRecord::upsert(search.clone().into(), pizza(10), pizza(12), &connection)
+                .await
+                .unwrap();
+        assert_eq!(created.price, 10);
+        let updated = DatabaseRecord::upsert(search.into(), pizza(10), pizza(12), &connection)
+            .await
+            .unwrap();
+        assert_eq!(updated.key(), created.key());
+        assert_eq!(updated.price, 12);
+        let queried: DatabaseRecord<Dish> = DatabaseRecord::find(created.key(), &connection)
+            .await
+            .unwrap();
+        assert_eq!(queried.price, 12);
+        let count = Dish::count(&Dish::query(), &connection).await.unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[maybe_async::test(
+        feature = "blocking",
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn creates_then_patches() {
+        let connection = common::setup_db().await;
+        let search = Comparison::field("name").equals_str("Pizza");
+        let patch = serde_json::json!({ "price": 15 });
+        let created =
+            DatabaseRecord::upsert_patch(search.clone().into(), pizza(10), &patch, &connection)
+                .await
+                .unwrap();
+        assert_eq!(created.price, 10);
+        let updated = DatabaseRecord::upsert_patch(search.into(), pizza(10), &patch, &connection)
+            .await
+            .unwrap();
+        assert_eq!(updated.key(), created.key());
+        assert_eq!(updated.price, 15);
+        assert_eq!(updated.description, "Italian Dish");
+    }
+}
+
 mod custom_key {
     use super::*;
 
@@ -1,7 +1,12 @@
 #[macro_use]
 extern crate aragog;
 
-use aragog::query::{Comparison, Filter, Query, SortDirection};
+use aragog::query::{
+    AggregateFunction, Collect, Comparison, Filter, GraphQueryDirection, Query, QueryOptions,
+    ScoringFunction, Search, SearchClause, SortDirection, TraversalOptions, TraversalOrder,
+    Uniqueness,
+};
+use std::time::Duration;
 
 pub mod common;
 
@@ -13,7 +18,7 @@ mod comparison {
         let item = Comparison::field("username").in_str_array(&["felix", "gerard"]);
         common::expect_assert_eq(
             item.aql_str("i").as_str(),
-            r#"i.username IN ["felix", "gerard"]"#,
+            r#"i.username IN ["felix","gerard"]"#,
         )?;
         Ok(())
     }
@@ -23,7 +28,7 @@ mod comparison {
         let item = Comparison::field("username").not_in_str_array(&["felix", "gerard"]);
         common::expect_assert_eq(
             item.aql_str("i").as_str(),
-            r#"i.username NOT IN ["felix", "gerard"]"#,
+            r#"i.username NOT IN ["felix","gerard"]"#,
         )?;
         Ok(())
     }
@@ -31,11 +36,11 @@ mod comparison {
     #[test]
     fn in_array() -> Result<(), String> {
         let item = Comparison::field("age").in_array(&[13, 14, 15]);
-        common::expect_assert_eq(item.aql_str("i").as_str(), r#"i.age IN [13, 14, 15]"#)?;
+        common::expect_assert_eq(item.aql_str("i").as_str(), r#"i.age IN [13,14,15]"#)?;
         let item = Comparison::field("price").in_array(&[13.1, 14.5, 16.13]);
         common::expect_assert_eq(
             item.aql_str("i").as_str(),
-            r#"i.price IN [13.1, 14.5, 16.13]"#,
+            r#"i.price IN [13.1,14.5,16.13]"#,
         )?;
         Ok(())
     }
@@ -43,11 +48,11 @@ mod comparison {
     #[test]
     fn not_in_array() -> Result<(), String> {
         let item = Comparison::field("age").not_in_array(&[13, 14, 15]);
-        common::expect_assert_eq(item.aql_str("i").as_str(), r#"i.age NOT IN [13, 14, 15]"#)?;
+        common::expect_assert_eq(item.aql_str("i").as_str(), r#"i.age NOT IN [13,14,15]"#)?;
         let item = Comparison::field("price").not_in_array(&[13.1, 14.5, 16.13]);
         common::expect_assert_eq(
             item.aql_str("i").as_str(),
-            r#"i.price NOT IN [13.1, 14.5, 16.13]"#,
+            r#"i.price NOT IN [13.1,14.5,16.13]"#,
         )?;
         Ok(())
     }
@@ -194,6 +199,73 @@ mod comparison {
             Ok(())
         }
     }
+
+    mod geo {
+        use super::*;
+
+        #[test]
+        fn within_distance() -> Result<(), String> {
+            let item = Comparison::field("location").within_distance(48.85, 2.35, 1000.0);
+            common::expect_assert_eq(
+                item.aql_str("i").as_str(),
+                "GEO_DISTANCE(i.location, [2.35,48.85]) <= 1000.0",
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn within_polygon() -> Result<(), String> {
+            let item = Comparison::field("location").within_polygon(&[
+                (48.0, 2.0),
+                (49.0, 2.0),
+                (49.0, 3.0),
+                (48.0, 2.0),
+            ]);
+            common::expect_assert_eq(
+                item.aql_str("i").as_str(),
+                r#"GEO_CONTAINS({"coordinates":[[[2.0,48.0],[2.0,49.0],[3.0,49.0],[2.0,48.0]]],"type":"Polygon"}, i.location)"#,
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn intersects() -> Result<(), String> {
+            let item = Comparison::field("area")
+                .intersects(serde_json::json!({ "type": "Point", "coordinates": [2.0, 48.0] }));
+            common::expect_assert_eq(
+                item.aql_str("i").as_str(),
+                r#"GEO_INTERSECTS({"coordinates":[2.0,48.0],"type":"Point"}, i.area)"#,
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn query_binds_geo_values() -> Result<(), String> {
+            let query = Query::new("Stores")
+                .filter(
+                    compare!(field "location")
+                        .within_distance(48.85, 2.35, 1000.0)
+                        .into(),
+                )
+                .sort_by_distance("location", 48.85, 2.35, None)
+                .join_outbound(
+                    1,
+                    1,
+                    false,
+                    Query::new("SoldIn").filter(compare!(field "price").greater_than(3).into()),
+                );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR b in Stores FILTER GEO_DISTANCE(b.location, @aragog_2) <= @aragog_1 SORT GEO_DISTANCE(b.location, @aragog_3) ASC FOR a in 1..1 OUTBOUND b SoldIn FILTER a.price > @aragog_0 return a",
+            )?;
+            common::expect_assert_eq(query.bind_vars.len(), 4)?;
+            common::expect_assert_eq(
+                query.bind_vars["aragog_3"].clone(),
+                serde_json::json!([2.35, 48.85]),
+            )?;
+            Ok(())
+        }
+    }
 }
 
 mod filter {
@@ -226,8 +298,280 @@ mod filter {
             i.company_name NOT LIKE \"%google%\" && \
             i.company_age > 15 || \
             i.emails ANY LIKE \"%gmail.com\" && \
-            i.roles IN [\"SHIPPER\", \"FORWARDER\"]",
+            i.roles IN [\"SHIPPER\",\"FORWARDER\"]",
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn groups_and_negations_are_parenthesized() -> Result<(), String> {
+        let filter = Filter::group(
+            Filter::new(Comparison::field("owner").equals_str("felix"))
+                .or(Comparison::any("shared_with").equals_str("felix")),
+        )
+        .and_filter(
+            !Filter::new(Comparison::field("archived").eq_true())
+                .and(Comparison::field("locked").eq_true()),
+        )
+        .or_filter(Filter::new(Comparison::field("public").eq_true()));
+        common::expect_assert_eq(
+            filter.aql_str("i").as_str(),
+            "\
+            (i.owner == \"felix\" || i.shared_with ANY == \"felix\") && \
+            NOT (i.archived == true && i.locked == true) || \
+            (i.public == true)",
+        )?;
+        common::expect_assert_eq((!!filter.clone()).aql_str("i"), filter.aql_str("i"))?;
+        Ok(())
+    }
+
+    #[test]
+    fn nested_values_are_bound() -> Result<(), String> {
+        let query = Query::new("Documents").filter(
+            !Filter::group(
+                Filter::new(Comparison::field("owner").equals_str("felix"))
+                    .or(Comparison::field("level").greater_than(3)),
+            )
+            .and(Comparison::field("public").eq_false()),
+        );
+        common::expect_assert_eq(
+            query.aql_str().as_str(),
+            "FOR a in Documents FILTER NOT ((a.owner == @aragog_0 || a.level > @aragog_1) && a.public == false) return a",
+        )?;
+        common::expect_assert_eq(query.bind_vars["aragog_0"].as_str(), Some("felix"))?;
+        common::expect_assert_eq(query.bind_vars["aragog_1"].as_i64(), Some(3))?;
+        Ok(())
+    }
+}
+
+mod evaluation {
+    use aragog::query::Filter;
+    use serde_json::json;
+
+    use super::*;
+
+    fn user() -> serde_json::Value {
+        json!({
+            "name": "Felix",
+            "age": 31,
+            "admin": false,
+            "emails": ["felix@gmail.com", "felix@company.com"],
+            "address": { "city": "Paris", "location": [2.35, 48.85] },
+            "scores": [12, 18, 7],
+        })
+    }
+
+    #[test]
+    fn comparisons_follow_aql_semantics() -> Result<(), String> {
+        let user = user();
+        let matches = |comparison: Comparison| comparison.evaluate(&user).unwrap();
+        common::expect_assert(matches(Comparison::field("age").equals(31.0)))?;
+        common::expect_assert(matches(
+            Comparison::field("address.city").equals_str("Paris"),
+        ))?;
+        common::expect_assert(matches(Comparison::field("scores[-1]").lesser_than(10)))?;
+        common::expect_assert(matches(
+            Comparison::field("name").in_str_array(&["Felix", "Gerard"]),
+        ))?;
+        common::expect_assert(!matches(Comparison::field("age").in_str_array(&["31"])))?;
+        // Missing fields are null, lower than any other value
+        common::expect_assert(matches(Comparison::field("height").eq_null()))?;
+        common::expect_assert(matches(Comparison::field("height").lesser_than(0)))?;
+        common::expect_assert(matches(Comparison::field("admin").lesser_than(0)))?;
+        // Strings are greater than any number
+        common::expect_assert(matches(Comparison::field("name").greater_than(100)))?;
+        common::expect_assert(matches(Comparison::field("name").like("F_l%")))?;
+        common::expect_assert(!matches(Comparison::field("name").like("f%")))?;
+        common::expect_assert(matches(Comparison::field("age").like("3%")))?;
+        common::expect_assert(matches(Comparison::field("name").matches("^Fe")))?;
+        common::expect_assert(matches(Comparison::field("name").does_not_match("z")))?;
+        Ok(())
+    }
+
+    #[test]
+    fn array_comparisons_work() -> Result<(), String> {
+        let user = user();
+        let matches = |comparison: Comparison| comparison.evaluate(&user).unwrap();
+        common::expect_assert(matches(Comparison::any("emails").like("%@gmail.com")))?;
+        common::expect_assert(!matches(Comparison::all("emails").like("%@gmail.com")))?;
+        common::expect_assert(matches(Comparison::none("scores").greater_than(20)))?;
+        common::expect_assert(matches(Comparison::all("scores").greater_than(5)))?;
+        // Array comparison operators are false for non array values
+        common::expect_assert(!matches(Comparison::none("name").equals_str("Gerard")))?;
+        Ok(())
+    }
+
+    #[test]
+    fn filters_follow_precedence_and_negation() -> Result<(), String> {
+        let user = user();
+        // false && false || true
+        let filter = Filter::new(Comparison::field("admin").eq_true())
+            .and(Comparison::field("age").greater_than(40))
+            .or(Comparison::field("name").equals_str("Felix"));
+        common::expect_assert(filter.evaluate(&user).unwrap())?;
+        common::expect_assert(!(!filter).evaluate(&user).unwrap())?;
+        // false && (false || true)
+        let filter = Filter::new(Comparison::field("admin").eq_true()).and_filter(
+            Filter::new(Comparison::field("age").greater_than(40))
+                .or(Comparison::field("name").equals_str("Felix")),
+        );
+        common::expect_assert(!filter.evaluate(&user).unwrap())?;
+        let filter = Filter::new(Comparison::field("age").greater_than(18))
+            .and_filter(!Filter::new(Comparison::field("admin").eq_true()));
+        common::expect_assert(filter.evaluate(&user).unwrap())?;
+        Ok(())
+    }
+
+    #[test]
+    fn geo_comparisons_work() -> Result<(), String> {
+        let user = user();
+        let matches = |comparison: Comparison| comparison.evaluate(&user).unwrap();
+        // Paris - Versailles is about 17km
+        common::expect_assert(matches(
+            Comparison::field("address.location").within_distance(48.80, 2.13, 20_000.0),
+        ))?;
+        common::expect_assert(!matches(
+            Comparison::field("address.location").within_distance(48.80, 2.13, 10_000.0),
+        ))?;
+        common::expect_assert(matches(
+            Comparison::field("address.location").within_polygon(&[
+                (48.0, 2.0),
+                (49.0, 2.0),
+                (49.0, 3.0),
+                (48.0, 3.0),
+            ]),
+        ))?;
+        common::expect_assert(!matches(
+            Comparison::field("address.location").within_polygon(&[
+                (48.0, 3.0),
+                (49.0, 3.0),
+                (49.0, 4.0),
+            ]),
+        ))?;
+        common::expect_assert(matches(Comparison::field("address.location").intersects(
+            json!({ "type": "LineString", "coordinates": [[2.0, 48.85], [3.0, 48.85]] }),
+        )))?;
+        Ok(())
+    }
+
+    #[test]
+    fn path_and_records_are_evaluated() -> Result<(), String> {
+        let filter = Filter::new(Comparison::all_path_edges("kind").equals_str("friend"))
+            .and(Comparison::path_vertex(0, "name").equals_str("Felix"));
+        let path = json!({
+            "vertices": [{ "name": "Felix" }, { "name": "Gerard" }],
+            "edges": [{ "kind": "friend" }],
+        });
+        common::expect_assert(
+            filter
+                .evaluate_traversal(&json!({ "name": "Gerard" }), &path)
+                .unwrap(),
+        )?;
+        common::expect_assert(filter.evaluate(&json!({ "name": "Gerard" })).is_err())?;
+        let filter = Filter::new(Comparison::field("name").equals_str("Felix"));
+        common::expect_assert(filter.evaluate_record(&user()).unwrap())?;
+        Ok(())
+    }
+
+    #[test]
+    fn unevaluable_comparisons_fail() -> Result<(), String> {
+        let user = user();
+        common::expect_assert(
+            Comparison::statement("LENGTH(a.emails)")
+                .greater_than(1)
+                .evaluate(&user)
+                .is_err(),
+        )?;
+        common::expect_assert(
+            Comparison::field("name")
+                .matches("(")
+                .evaluate(&user)
+                .is_err(),
+        )?;
+        Ok(())
+    }
+}
+
+mod field_paths {
+    use aragog::query::Fields;
+    use aragog::Record;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Clone, Serialize, Deserialize, aragog::Fields)]
+    pub struct Address {
+        pub city: String,
+        #[serde(rename = "zip")]
+        pub zip_code: Option<u32>,
+    }
+
+    #[derive(Clone, Serialize, Deserialize, aragog::Fields)]
+    pub struct Timestamps {
+        pub created_at: i64,
+    }
+
+    #[derive(Clone, Serialize, Deserialize, Record)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Customer {
+        pub first_name: String,
+        pub age: u16,
+        pub active: bool,
+        pub tags: Vec<String>,
+        #[serde(rename(serialize = "mail", deserialize = "email"))]
+        pub email: String,
+        #[nested_fields]
+        pub address: Address,
+        #[nested_fields]
+        pub billing_address: Option<Address>,
+        #[serde(flatten)]
+        pub timestamps: Timestamps,
+        // Skipped fields have no path
+        #[allow(dead_code)]
+        #[serde(skip)]
+        pub cache: Option<String>,
+    }
+
+    #[test]
+    fn paths_honor_serde_attributes() -> Result<(), String> {
+        let fields = Customer::fields();
+        common::expect_assert_eq(fields.first_name().path(), "firstName")?;
+        common::expect_assert_eq(fields.email().path(), "mail")?;
+        common::expect_assert_eq(fields.address().city().path(), "address.city")?;
+        common::expect_assert_eq(fields.address().zip_code().path(), "address.zip")?;
+        common::expect_assert_eq(
+            fields.billing_address().city().path(),
+            "billingAddress.city",
+        )?;
+        common::expect_assert_eq(fields.timestamps().created_at().path(), "created_at")?;
+        Ok(())
+    }
+
+    #[test]
+    fn typed_comparisons_work() -> Result<(), String> {
+        let fields = Customer::fields();
+        let query = Customer::query()
+            .filter(
+                Filter::new(fields.age().greater_or_equal(18))
+                    .and(fields.active().eq_true())
+                    .and(fields.first_name().like("F%"))
+                    .and(fields.address().zip_code().not_null())
+                    .and(fields.tags().any().equals("vip")),
+            )
+            .filter(
+                fields
+                    .address()
+                    .city()
+                    .in_array(&["Paris".to_string()])
+                    .into(),
+            )
+            .sort(fields.timestamps().created_at().path(), None);
+        common::expect_assert_eq(
+            query.aql_str().as_str(),
+            "FOR a in Customer FILTER a.age >= @aragog_0 && a.active == true && a.firstName LIKE @aragog_1 && a.address.zip != null && a.tags ANY == @aragog_2 FILTER a.address.city IN @aragog_3 SORT a.created_at ASC return a",
         )?;
+        common::expect_assert_eq(query.bind_vars["aragog_0"].as_u64(), Some(18))?;
+        common::expect_assert_eq(query.bind_vars["aragog_2"].as_str(), Some("vip"))?;
         Ok(())
     }
 }
@@ -255,11 +599,11 @@ mod query {
                 query.aql_str().as_str(),
                 "\
             FOR b in Companies \
-                FILTER b.emails ANY LIKE \"%gmail.com\" \
+                FILTER b.emails ANY LIKE @aragog_1 \
                 SORT b.company_name ASC \
                     FOR a in 1..2 OUTBOUND b MemberOf \
                         SORT a._id ASC \
-                        PRUNE 1 == 1 \
+                        PRUNE 1 == @aragog_0 \
                         return a",
             )?;
             Ok(())
@@ -293,17 +637,33 @@ mod query {
                 query.aql_str().as_str(),
                 "\
             FOR d in Companies \
-                FILTER d.emails ANY LIKE \"%gmail.com\" \
+                FILTER d.emails ANY LIKE @aragog_1 \
                 SORT d.company_name ASC \
                     FOR c in 1..2 OUTBOUND d MemberOf \
                         SORT c._id ASC \
-                        FILTER 1 == 1 \
+                        FILTER 1 == @aragog_0 \
                             FOR b in 1..5 INBOUND c BelongsTo \
                                 FOR a in 2..2 OUTBOUND b HasFriend \
                                 return a",
             )?;
             Ok(())
         }
+
+        #[test]
+        fn k_paths_are_not_weighted() -> Result<(), String> {
+            let query = Query::k_paths(
+                1,
+                4,
+                GraphQueryDirection::Inbound,
+                "City/1",
+                "City/2",
+                "Road",
+                false,
+            );
+            let weighted_query = query.clone().weighted("distance", 1.0);
+            common::expect_assert_eq(weighted_query.aql_str(), query.aql_str())?;
+            Ok(())
+        }
     }
 
     mod named_graph {
@@ -326,11 +686,11 @@ mod query {
                 query.aql_str().as_str(),
                 "\
             FOR b in Companies \
-                FILTER b.emails ANY LIKE \"%gmail.com\" \
+                FILTER b.emails ANY LIKE @aragog_1 \
                 SORT b.company_name ASC \
                     FOR a in 1..2 OUTBOUND b GRAPH GraphName \
                         SORT a._id ASC \
-                        PRUNE 1 == 1 \
+                        PRUNE 1 == @aragog_0 \
                         return a",
             )?;
             Ok(())
@@ -364,11 +724,11 @@ mod query {
                 query.aql_str().as_str(),
                 "\
             FOR d in Companies \
-                FILTER d.emails ANY LIKE \"%gmail.com\" \
+                FILTER d.emails ANY LIKE @aragog_1 \
                 SORT d.company_name ASC \
                     FOR c in 1..2 OUTBOUND d GRAPH SomeGraph \
                         SORT c._id ASC \
-                        FILTER 1 == 1 \
+                        FILTER 1 == @aragog_0 \
                             FOR b in 1..5 INBOUND c BelongsTo \
                                 FOR a in 2..2 OUTBOUND b GRAPH OtherGraph \
                                     return a",
@@ -389,7 +749,7 @@ mod query {
             query.aql_str().as_str(),
             "\
         FOR a in Companies \
-            FILTER a.emails ANY LIKE \"%gmail.com\" \
+            FILTER a.emails ANY LIKE @aragog_0 \
             SORT a.company_name ASC, a.company_age DESC \
             LIMIT 5 \
             return DISTINCT a",
@@ -408,7 +768,7 @@ mod query {
         common::expect_assert_eq(
             query.aql_str().as_str(),
             "FOR a in Companies \
-                        FILTER a.emails ANY LIKE \"%gmail.com\" \
+                        FILTER a.emails ANY LIKE @aragog_0 \
                         SORT a.company_name ASC, a.company_age DESC \
                         LIMIT 5 \
                         return DISTINCT a",
@@ -429,7 +789,7 @@ mod query {
                     FILTER a.active == true \
                     SORT a.age ASC \
                     LIMIT 5 \
-                    FILTER a.gender == \"f\" \
+                    FILTER a.gender == @aragog_0 \
                     return a",
         )?;
         Ok(())
@@ -450,7 +810,7 @@ mod query {
         common::expect_assert_eq(
             query.aql_str().as_str(),
             "FOR a in Companies \
-                       FILTER a.emails ANY LIKE \"%gmail.com\" && a.id > 10 \
+                       FILTER a.emails ANY LIKE @aragog_0 && a.id > @aragog_1 \
                        SORT a.company_name DESC, a.company_age ASC \
                        LIMIT 5 \
                        return DISTINCT a",
@@ -458,85 +818,794 @@ mod query {
         Ok(())
     }
 
-    #[test]
-    fn empty_query_works() -> Result<(), String> {
-        let query = Query::new("Companies");
-        common::expect_assert_eq(query.aql_str().as_str(), "FOR a in Companies return a")?;
-        Ok(())
-    }
-}
+    mod bind_vars {
+        use super::*;
+        use serde_json::json;
 
-mod call {
-    use serde::{Deserialize, Serialize};
+        #[test]
+        fn values_are_bound() -> Result<(), String> {
+            let query = Query::new("Users").filter(
+                compare!(field "username")
+                    .equals_str("felix\" || true || \"")
+                    .and(compare!(field "age").in_array(&[18, 19])),
+            );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Users FILTER a.username == @aragog_0 && a.age IN @aragog_1 return a",
+            )?;
+            common::expect_assert_eq(query.bind_vars.len(), 2)?;
+            common::expect_assert_eq(
+                &query.bind_vars["aragog_0"],
+                &json!("felix\" || true || \""),
+            )?;
+            common::expect_assert_eq(&query.bind_vars["aragog_1"], &json!([18, 19]))?;
+            Ok(())
+        }
 
-    use aragog::{DatabaseConnection, DatabaseRecord, Record};
+        #[test]
+        fn literals_are_not_bound() -> Result<(), String> {
+            let query = Query::new("Users").filter(
+                compare!(field "active")
+                    .eq_true()
+                    .and(compare!(field "name").not_null()),
+            );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Users FILTER a.active == true && a.name != null return a",
+            )?;
+            common::expect_assert(query.bind_vars.is_empty())?;
+            Ok(())
+        }
 
-    use super::*;
-    use aragog::query::{QueryCursor, QueryResult};
+        #[test]
+        fn custom_vars_are_kept() -> Result<(), String> {
+            let query = Query::new("Users")
+                .bind_var("aragog_0", "custom")
+                .filter(compare!(field "age").greater_than(10).into());
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Users FILTER a.age > @aragog_1 return a",
+            )?;
+            common::expect_assert_eq(&query.bind_vars["aragog_0"], &json!("custom"))?;
+            common::expect_assert_eq(&query.bind_vars["aragog_1"], &json!(10))?;
+            Ok(())
+        }
 
-    #[derive(Clone, Serialize, Deserialize, Record)]
-    pub struct Dish {
-        pub name: String,
+        #[test]
+        fn joined_queries_vars_do_not_collide() -> Result<(), String> {
+            let query = Query::new("Companies")
+                .filter(compare!(field "name").equals_str("Qonfucius").into())
+                .join_outbound(
+                    1,
+                    1,
+                    false,
+                    Query::new("MemberOf").filter(compare!(field "age").greater_than(18).into()),
+                );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR b in Companies \
+                    FILTER b.name == @aragog_1 \
+                        FOR a in 1..1 OUTBOUND b MemberOf \
+                            FILTER a.age > @aragog_0 \
+                            return a",
+            )?;
+            common::expect_assert_eq(&query.bind_vars["aragog_0"], &json!(18))?;
+            common::expect_assert_eq(&query.bind_vars["aragog_1"], &json!("Qonfucius"))?;
+            Ok(())
+        }
     }
 
-    #[derive(Clone, Serialize, Deserialize, Record)]
-    pub struct Order {
-        pub name: String,
-    }
+    mod projection {
+        use super::*;
 
-    #[derive(Clone, Serialize, Deserialize, Record)]
-    pub struct PartOf {}
+        #[test]
+        fn select_works() -> Result<(), String> {
+            let query = Query::new("Users")
+                .filter(compare!(field "age").greater_than(10).into())
+                .select(&["username", "address.city"]);
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                r#"FOR a in Users FILTER a.age > @aragog_0 return { "username": a.username, "address": { "city": a.address.city } }"#,
+            )?;
+            Ok(())
+        }
 
-    fn linker() -> PartOf {
-        PartOf {}
+        #[test]
+        fn select_nests_dotted_fields() -> Result<(), String> {
+            let query = Query::new("Users").select(&[
+                "address.city",
+                "name",
+                "address.geo.lat",
+                "address.geo.lng",
+                "company",
+                "company.name",
+            ]);
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                r#"FOR a in Users return { "address": { "city": a.address.city, "geo": { "lat": a.address.geo.lat, "lng": a.address.geo.lng } }, "name": a.name, "company": a.company }"#,
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn return_expr_works() -> Result<(), String> {
+            let query = Query::new("Users").return_expr("a.age * 2").distinct();
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Users return DISTINCT a.age * 2",
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn sub_query_projection_is_used() -> Result<(), String> {
+            let query = Query::new("Users").join_outbound(
+                1,
+                1,
+                false,
+                Query::new("MemberOf").select(&["name"]),
+            );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                r#"FOR b in Users FOR a in 1..1 OUTBOUND b MemberOf return { "name": a.name }"#,
+            )?;
+            Ok(())
+        }
     }
 
-    #[maybe_async::maybe_async]
-    async fn factory(db_connection: &DatabaseConnection) {
-        let p1 = DatabaseRecord::create(
-            Dish {
-                name: "Pizza Mozarella".to_string(),
-            },
-            db_connection,
-        )
-        .await
-        .unwrap();
-        let p2 = DatabaseRecord::create(
-            Dish {
-                name: "Pizza Regina".to_string(),
-            },
-            db_connection,
-        )
-        .await
-        .unwrap();
-        let ic = DatabaseRecord::create(
-            Dish {
-                name: "Ice Cream".to_string(),
-            },
-            db_connection,
-        )
-        .await
-        .unwrap();
-        let wi = DatabaseRecord::create(
-            Dish {
-                name: "Wine".to_string(),
-            },
-            db_connection,
-        )
-        .await
-        .unwrap();
-        let pa = DatabaseRecord::create(
-            Dish {
-                name: "Spaghetti".to_string(),
-            },
-            db_connection,
-        )
-        .await
-        .unwrap();
+    mod collect {
+        use super::*;
 
-        let m1 = DatabaseRecord::create(
-            Order {
-                name: "Menu Pizza".to_string(),
+        #[test]
+        fn group_and_aggregate_works() -> Result<(), String> {
+            let query = Query::new("Orders")
+                .filter(compare!(field "paid").eq_true().into())
+                .collect(
+                    Collect::new()
+                        .group_by("country", "address.country")
+                        .group_by("city", "address.city")
+                        .aggregate("total", AggregateFunction::Sum, "price")
+                        .aggregate("average", AggregateFunction::Average, "price"),
+                )
+                .sort("total", Some(SortDirection::Desc))
+                .limit(10, None);
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Orders \
+                    FILTER a.paid == true \
+                    COLLECT country = a.address.country, city = a.address.city \
+                    AGGREGATE total = SUM(a.price), average = AVERAGE(a.price) \
+                    SORT total DESC \
+                    LIMIT 10 \
+                    return { \"country\": country, \"city\": city, \"total\": total, \"average\": average }",
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn count_works() -> Result<(), String> {
+            let query = Query::new("Orders").collect(Collect::new().count_into("length"));
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                r#"FOR a in Orders COLLECT WITH COUNT INTO length return { "length": length }"#,
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn into_group_works() -> Result<(), String> {
+            let query = Query::new("Orders").collect(
+                Collect::new()
+                    .group_by("user", "user_id")
+                    .into_group("orders"),
+            );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                r#"FOR a in Orders COLLECT user = a.user_id INTO orders = a return { "user": user, "orders": orders }"#,
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn projection_overrides_collect() -> Result<(), String> {
+            let query = Query::new("Orders")
+                .collect(Collect::new().group_by("user", "user_id"))
+                .return_expr("user");
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Orders COLLECT user = a.user_id return user",
+            )?;
+            Ok(())
+        }
+    }
+
+    mod search {
+        use super::*;
+
+        #[test]
+        fn search_comes_before_operations() -> Result<(), String> {
+            let query = Query::new("DishSearch")
+                .filter(compare!(field "price").lesser_than(20).into())
+                .search(
+                    Search::new(SearchClause::field("name").starts_with("Pizza"))
+                        .or(SearchClause::field("tags").any_token("wood fired", "text_en")),
+                )
+                .sort_by_score(ScoringFunction::Tfidf, Some(SortDirection::Desc))
+                .sort("name", None)
+                .limit(10, None);
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                r#"FOR a in DishSearch SEARCH STARTS_WITH(a.name, @aragog_1) || a.tags IN TOKENS(@aragog_2, "text_en") FILTER a.price < @aragog_0 SORT TFIDF(a) DESC, a.name ASC LIMIT 10 return a"#,
+            )?;
+            common::expect_assert_eq(query.bind_vars["aragog_1"].as_str(), Some("Pizza"))?;
+            common::expect_assert_eq(query.bind_vars["aragog_2"].as_str(), Some("wood fired"))?;
+            Ok(())
+        }
+
+        #[test]
+        fn search_replaces_previous_search() -> Result<(), String> {
+            let query = Query::new("DishSearch")
+                .search(search!("name").phrase("pizza").into())
+                .search(search!("description").exists().into());
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in DishSearch SEARCH EXISTS(a.description) return a",
+            )?;
+            common::expect_assert(query.bind_vars.is_empty())?;
+            Ok(())
+        }
+
+        #[test]
+        fn joined_search_keeps_bind_vars() -> Result<(), String> {
+            let query = Query::new("DishSearch")
+                .search(search!("name").phrase("pizza").into())
+                .join_outbound(
+                    1,
+                    1,
+                    false,
+                    Query::new("PartOf").filter(compare!(field "price").equals(10).into()),
+                );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR b in DishSearch SEARCH PHRASE(b.name, @aragog_1) FOR a in 1..1 OUTBOUND b PartOf FILTER a.price == @aragog_0 return a",
+            )?;
+            common::expect_assert_eq(query.bind_vars["aragog_0"].as_i64(), Some(10))?;
+            common::expect_assert_eq(query.bind_vars["aragog_1"].as_str(), Some("pizza"))?;
+            Ok(())
+        }
+    }
+
+    mod join_on {
+        use super::*;
+
+        #[test]
+        fn foreign_key_join_works() -> Result<(), String> {
+            let query = Query::new("Users")
+                .filter(compare!(field "age").greater_than(18).into())
+                .join_on(
+                    Query::new("Orders")
+                        .filter(compare!(field "total").greater_than(100).into())
+                        .sort("total", Some(SortDirection::Desc))
+                        .return_paired(),
+                    "_key",
+                    "user_id",
+                );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR b in Users FILTER b.age > @aragog_1 FOR a in Orders FILTER a.user_id == b._key FILTER a.total > @aragog_0 SORT a.total DESC return [b, a]",
+            )?;
+            common::expect_assert_eq(query.bind_vars["aragog_0"].as_i64(), Some(100))?;
+            common::expect_assert_eq(query.bind_vars["aragog_1"].as_i64(), Some(18))?;
+            Ok(())
+        }
+
+        #[test]
+        fn nested_joins_work() -> Result<(), String> {
+            let query = Query::new("Users").join_on(
+                Query::new("Orders").join_on(
+                    Query::new("Products").return_merged(),
+                    "product_id",
+                    "_key",
+                ),
+                "_key",
+                "user_id",
+            );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR c in Users FOR b in Orders FILTER b.user_id == c._key FOR a in Products FILTER a._key == b.product_id return MERGE(b, a)",
+            )?;
+            Ok(())
+        }
+    }
+
+    mod let_subquery {
+        use super::*;
+
+        #[test]
+        fn let_expr_works() -> Result<(), String> {
+            let query = Query::new("Users")
+                .let_expr("tag_count", "LENGTH(a.tags)")
+                .filter(compare!("tag_count").greater_than(2).into())
+                .sort("tag_count", Some(SortDirection::Desc))
+                .sort("name", None);
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Users LET tag_count = LENGTH(a.tags) FILTER tag_count > @aragog_0 SORT tag_count DESC, a.name ASC return a",
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn correlated_subquery_works() -> Result<(), String> {
+            let query = Query::new("Users")
+                .filter(compare!(field "age").greater_than(18).into())
+                .let_subquery_on(
+                    "orders",
+                    Query::new("Orders").filter(compare!(field "total").greater_than(100).into()),
+                    "_key",
+                    "user_id",
+                )
+                .filter(compare!("LENGTH(orders)").greater_than(3).into())
+                .return_expr("MERGE(b, { orders })");
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR b in Users FILTER b.age > @aragog_0 LET orders = (FOR a in Orders FILTER a.user_id == b._key FILTER a.total > @aragog_0_orders return a) FILTER LENGTH(orders) > @aragog_2 return MERGE(b, { orders })",
+            )?;
+            common::expect_assert_eq(query.bind_vars["aragog_0"].as_i64(), Some(18))?;
+            common::expect_assert_eq(query.bind_vars["aragog_0_orders"].as_i64(), Some(100))?;
+            common::expect_assert_eq(query.bind_vars["aragog_2"].as_i64(), Some(3))?;
+            Ok(())
+        }
+
+        #[test]
+        fn subquery_identifiers_dont_collide() -> Result<(), String> {
+            let query = Query::new("Users")
+                .let_subquery(
+                    "products",
+                    Query::new("Orders").join_on(Query::new("Products"), "product_id", "_key"),
+                )
+                .join_on(Query::new("Profiles").return_merged(), "_key", "user_id");
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR c in Users LET products = (FOR b in Orders FOR a in Products FILTER a._key == b.product_id return a) FOR a in Profiles FILTER a.user_id == c._key return MERGE(c, a)",
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn document_placeholder_follows_the_identifier() -> Result<(), String> {
+            let query = Query::new("Users")
+                .let_expr("tag_count", "LENGTH($doc.tags)")
+                .let_subquery_on("orders", Query::new("Orders"), "_key", "user_id")
+                .let_subquery(
+                    "items",
+                    Query::new("Orders").join_on(Query::new("Items"), "_key", "order_id"),
+                );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR c in Users LET tag_count = LENGTH(c.tags) LET orders = (FOR a in Orders FILTER a.user_id == c._key return a) LET items = (FOR b in Orders FOR a in Items FILTER a.order_id == b._key return a) return c",
+            )?;
+            let query = Query::new("Users").let_expr("docs", "[$doc, $docs]");
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Users LET docs = [a, $docs] return a",
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn nested_subqueries_work() -> Result<(), String> {
+            let query = Query::new("Users").let_subquery_on(
+                "orders",
+                Query::new("Orders")
+                    .let_subquery(
+                        "items",
+                        Query::new("Items").filter(compare!(field "price").greater_than(10).into()),
+                    )
+                    .filter(compare!("LENGTH(items)").greater_than(1).into()),
+                "_key",
+                "user_id",
+            );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR c in Users LET orders = (FOR b in Orders FILTER b.user_id == c._key LET items = (FOR a in Items FILTER a.price > @aragog_0_items_orders return a) FILTER LENGTH(items) > @aragog_1_orders return b) return c",
+            )?;
+            common::expect_assert_eq(query.bind_vars.len(), 2)?;
+            Ok(())
+        }
+    }
+
+    mod sort {
+        use super::*;
+        use aragog::query::Pagination;
+
+        #[test]
+        fn multi_key_sort_works() -> Result<(), String> {
+            let query = Query::new("Users")
+                .filter(compare!(field "age").greater_than(18).into())
+                .sort_by(&[
+                    ("last_name", SortDirection::Asc),
+                    ("first_name", SortDirection::Asc),
+                    ("age", SortDirection::Desc),
+                ])
+                .limit(10, None);
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Users FILTER a.age > @aragog_0 SORT a.last_name ASC, a.first_name ASC, a.age DESC LIMIT 10 return a",
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn expression_sort_works() -> Result<(), String> {
+            let query = Query::new("Articles")
+                .sort_expr("LENGTH(a.tags)", Some(SortDirection::Desc))
+                .sort_by(&[("title", SortDirection::Asc)])
+                .sort_expr("a.views / a.days", None);
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Articles SORT LENGTH(a.tags) DESC, a.title ASC, a.views / a.days ASC return a",
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn sample_works() -> Result<(), String> {
+            let query = Query::new("Articles")
+                .sort("title", None)
+                .sample(3)
+                .sort("views", Some(SortDirection::Desc));
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Articles SORT a.title ASC SORT RAND() LIMIT 3 SORT a.views DESC return a",
+            )?;
+            common::expect_assert(
+                query
+                    .paginated(&Pagination::new("title", SortDirection::Asc, 10))
+                    .is_err(),
+            )?;
+            Ok(())
+        }
+    }
+
+    mod pagination {
+        use super::*;
+        use aragog::query::Pagination;
+
+        #[test]
+        fn first_page_is_sorted_and_limited() -> Result<(), String> {
+            let query = Query::new("Dish")
+                .filter(compare!(field "price").lesser_than(10).into())
+                .paginated(&Pagination::new("price", SortDirection::Asc, 10))
+                .unwrap();
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Dish FILTER a.price < @aragog_0 SORT a.price ASC, a._key ASC LIMIT 11 return [a, a.price]",
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn invalid_pages_are_refused() -> Result<(), String> {
+            let pagination = Pagination::new("price", SortDirection::Asc, 10);
+            common::expect_assert(
+                Query::new("Dish")
+                    .sort("name", None)
+                    .paginated(&pagination)
+                    .is_err(),
+            )?;
+            common::expect_assert(
+                Query::new("Dish")
+                    .limit(5, None)
+                    .paginated(&pagination)
+                    .is_err(),
+            )?;
+            common::expect_assert(
+                Query::new("Dish")
+                    .paginated(&pagination.clone().after(Some("not a token")))
+                    .is_err(),
+            )?;
+            Ok(())
+        }
+
+        #[test]
+        fn page_size_is_checked() -> Result<(), String> {
+            common::expect_assert(
+                Query::new("Dish")
+                    .paginated(&Pagination::new("price", SortDirection::Asc, 0))
+                    .is_err(),
+            )?;
+            let query = Query::new("Dish")
+                .paginated(&Pagination::new("price", SortDirection::Asc, u32::MAX))
+                .unwrap();
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Dish SORT a.price ASC, a._key ASC LIMIT 4294967295 return [a, a.price]",
+            )?;
+            Ok(())
+        }
+    }
+
+    mod traversal_options {
+        use super::*;
+
+        #[test]
+        fn options_and_path_filters_work() -> Result<(), String> {
+            let query = Query::outbound(1, 4, "Follows", "User/123")
+                .traversal_options(
+                    TraversalOptions::new()
+                        .unique_vertices(Uniqueness::Global)
+                        .unique_edges(Uniqueness::Path)
+                        .order(TraversalOrder::Bfs),
+                )
+                .filter(
+                    Comparison::all_path_edges("kind")
+                        .equals_str("friend")
+                        .and(Comparison::path_vertex(1, "age").greater_or_equal(18)),
+                );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                r#"FOR a, a_edge, a_path in 1..4 OUTBOUND 'User/123' Follows OPTIONS { uniqueVertices: "global", uniqueEdges: "path", order: "bfs" } FILTER a_path.edges[*].kind ALL == @aragog_0 && a_path.vertices[1].age >= @aragog_1 return a"#,
+            )?;
+            common::expect_assert_eq(query.bind_vars["aragog_0"].as_str(), Some("friend"))?;
+            Ok(())
+        }
+
+        #[test]
+        fn joined_options_follow_prune() -> Result<(), String> {
+            let query = Query::new("Users")
+                .filter(compare!(field "name").equals_str("Felix").into())
+                .join_any(
+                    1,
+                    3,
+                    true,
+                    Query::new("SocialGraph")
+                        .prune(Comparison::path_edge(0, "weight").greater_than(5).into())
+                        .traversal_options(
+                            TraversalOptions::new()
+                                .weighted("weight", 1.0)
+                                .edge_collections(&["Follows"]),
+                        )
+                        .limit(10, None),
+                );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                r#"FOR b in Users FILTER b.name == @aragog_1 FOR a, a_edge, a_path in 1..3 ANY b GRAPH SocialGraph PRUNE a_path.edges[0].weight > @aragog_0 OPTIONS { order: "weighted", weightAttribute: "weight", defaultWeight: 1.0, edgeCollections: ["Follows"] } LIMIT 10 return a"#,
+            )?;
+            common::expect_assert_eq(query.bind_vars["aragog_0"].as_i64(), Some(5))?;
+            Ok(())
+        }
+
+        #[test]
+        fn options_follow_every_prune() -> Result<(), String> {
+            let query = Query::outbound(1, 3, "Follows", "User/123")
+                .filter(compare!(field "active").eq_true().into())
+                .prune(compare!(field "age").lesser_than(18).into())
+                .traversal_options(TraversalOptions::new().order(TraversalOrder::Bfs));
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                r#"FOR a in 1..3 OUTBOUND 'User/123' Follows PRUNE a.age < @aragog_0 OPTIONS { order: "bfs" } FILTER a.active == true return a"#,
+            )?;
+            Ok(())
+        }
+    }
+
+    mod query_spec {
+        use super::*;
+        use aragog::query::QuerySpec;
+        use serde_json::json;
+
+        const ALLOWED_FIELDS: [&str; 3] = ["name", "price", "tags"];
+
+        fn spec(value: serde_json::Value) -> QuerySpec {
+            serde_json::from_value(value).unwrap()
+        }
+
+        #[test]
+        fn spec_is_applied() -> Result<(), String> {
+            let query = spec(json!({
+                "filter": {
+                    "or": [
+                        { "and": [
+                            { "field": "price", "op": "lt", "value": 10 },
+                            { "field": "tags", "op": "in", "value": ["vegan", "veggie"] }
+                        ] },
+                        { "not": { "field": "name", "op": "like", "value": "Pizza%" } }
+                    ]
+                },
+                "sort": [{ "field": "price" }, { "field": "name", "direction": "desc" }],
+                "limit": 10,
+                "skip": 20
+            }))
+            .apply(Query::new("Dish"), &ALLOWED_FIELDS)
+            .unwrap();
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in Dish FILTER (a.price < @aragog_0 && a.tags IN @aragog_1) || NOT (a.name LIKE @aragog_2) SORT a.price ASC, a.name DESC LIMIT 20, 10 return a",
+            )?;
+            common::expect_assert_eq(
+                query.bind_vars["aragog_1"].clone(),
+                json!(["vegan", "veggie"]),
+            )?;
+            common::expect_assert_eq(query.bind_vars["aragog_2"].as_str(), Some("Pizza%"))?;
+            Ok(())
+        }
+
+        #[test]
+        fn invalid_specs_are_refused() -> Result<(), String> {
+            let invalid_specs = [
+                json!({ "filter": { "field": "owner", "op": "eq", "value": "felix" } }),
+                json!({ "filter": { "field": "name", "op": "in", "value": "Pizza" } }),
+                json!({ "filter": { "field": "name", "op": "like", "value": 12 } }),
+                json!({ "filter": { "and": [] } }),
+                json!({ "sort": [{ "field": "owner" }] }),
+                json!({ "skip": 10 }),
+                json!({ "limit": 1001 }),
+            ];
+            for invalid_spec in invalid_specs {
+                common::expect_assert(
+                    spec(invalid_spec)
+                        .apply(Query::new("Dish"), &ALLOWED_FIELDS)
+                        .is_err(),
+                )?;
+            }
+            common::expect_assert(
+                serde_json::from_value::<QuerySpec>(
+                    json!({ "filter": { "field": "name", "op": "==", "value": "a" } }),
+                )
+                .is_err(),
+            )?;
+            common::expect_assert(
+                serde_json::from_value::<QuerySpec>(json!({ "filter": { "and": [], "or": [] } }))
+                    .is_err(),
+            )?;
+            common::expect_assert(
+                serde_json::from_value::<QuerySpec>(
+                    json!({ "sort": [{ "field": "name", "direction": "Desc" }] }),
+                )
+                .is_err(),
+            )?;
+            common::expect_assert(
+                spec(json!({ "limit": 50 }))
+                    .apply_with_max_limit(Query::new("Dish"), &ALLOWED_FIELDS, 20)
+                    .is_err(),
+            )?;
+            Ok(())
+        }
+    }
+
+    mod aql_macro {
+        use super::*;
+        use serde_json::json;
+
+        #[test]
+        fn parameters_are_bound() -> Result<(), String> {
+            let min_age = 18;
+            let name = "felix";
+            let query = aragog::aql!(
+                "FOR u IN User FILTER u.age >= @min_age && u.name == @name RETURN u",
+                min_age,
+                name: String = name.to_string(),
+            );
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR u IN User FILTER u.age >= @min_age && u.name == @name RETURN u",
+            )?;
+            common::expect_assert_eq(query.bind_vars.len(), 2)?;
+            common::expect_assert_eq(&query.bind_vars["min_age"], &json!(18))?;
+            common::expect_assert_eq(&query.bind_vars["name"], &json!("felix"))?;
+            Ok(())
+        }
+
+        #[test]
+        fn collection_parameters_are_bound() -> Result<(), String> {
+            let query = aragog::aql!(
+                "FOR d IN @@collection LIMIT @count RETURN d.name",
+                @collection = "Dish",
+                count = 2,
+            );
+            common::expect_assert_eq(&query.bind_vars["@collection"], &json!("Dish"))?;
+            common::expect_assert_eq(&query.bind_vars["count"], &json!(2))?;
+            Ok(())
+        }
+
+        #[test]
+        fn macro_query_can_be_customized() -> Result<(), String> {
+            let query = aragog::aql!(
+                "FOR d IN Dish /* Sub query */ FILTER LIKE(d.name, 'Pizza%') RETURN d"
+            )
+            .filter(compare!(field "price").lesser_than(10).into())
+            .limit(5, None);
+            common::expect_assert_eq(
+                query.aql_str().as_str(),
+                "FOR a in (FOR d IN Dish /* Sub query */ FILTER LIKE(d.name, 'Pizza%') RETURN d) FILTER a.price < @aragog_0 LIMIT 5 return a",
+            )?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn empty_query_works() -> Result<(), String> {
+        let query = Query::new("Companies");
+        common::expect_assert_eq(query.aql_str().as_str(), "FOR a in Companies return a")?;
+        Ok(())
+    }
+}
+
+mod call {
+    use serde::{Deserialize, Serialize};
+
+    use aragog::{DatabaseConnection, DatabaseRecord, Record};
+
+    use super::*;
+    use aragog::query::{
+        GraphQueryDirection, Page, Pagination, Path, QueryCursor, QueryResult, TraversalItem,
+    };
+    use aragog::{EdgeRecord, UndefinedRecord};
+
+    #[derive(Clone, Serialize, Deserialize, Record)]
+    pub struct Dish {
+        pub name: String,
+    }
+
+    #[derive(Clone, Serialize, Deserialize, Record)]
+    pub struct Order {
+        pub name: String,
+    }
+
+    #[derive(Clone, Serialize, Deserialize, Record)]
+    pub struct PartOf {}
+
+    fn linker() -> PartOf {
+        PartOf {}
+    }
+
+    #[maybe_async::maybe_async]
+    async fn factory(db_connection: &DatabaseConnection) {
+        let p1 = DatabaseRecord::create(
+            Dish {
+                name: "Pizza Mozarella".to_string(),
+            },
+            db_connection,
+        )
+        .await
+        .unwrap();
+        let p2 = DatabaseRecord::create(
+            Dish {
+                name: "Pizza Regina".to_string(),
+            },
+            db_connection,
+        )
+        .await
+        .unwrap();
+        let ic = DatabaseRecord::create(
+            Dish {
+                name: "Ice Cream".to_string(),
+            },
+            db_connection,
+        )
+        .await
+        .unwrap();
+        let wi = DatabaseRecord::create(
+            Dish {
+                name: "Wine".to_string(),
+            },
+            db_connection,
+        )
+        .await
+        .unwrap();
+        let pa = DatabaseRecord::create(
+            Dish {
+                name: "Spaghetti".to_string(),
+            },
+            db_connection,
+        )
+        .await
+        .unwrap();
+
+        let m1 = DatabaseRecord::create(
+            Order {
+                name: "Menu Pizza".to_string(),
             },
             db_connection,
         )
@@ -678,6 +1747,560 @@ mod call {
         Ok(())
     }
 
+    #[cfg(all(feature = "stream", not(feature = "blocking")))]
+    #[tokio::test]
+    async fn streamed_request() -> Result<(), String> {
+        use futures::StreamExt;
+
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let cursor: QueryCursor<Dish> = Query::new("Dish")
+            .sort("name", None)
+            .call_in_batches(&connection, 2)
+            .await
+            .unwrap();
+        let names: Vec<String> = cursor
+            .into_stream()
+            .map(|dish| dish.unwrap().record.name)
+            .collect()
+            .await;
+        common::expect_assert_eq(
+            names,
+            vec![
+                "Ice Cream".to_string(),
+                "Pizza Mozarella".to_string(),
+                "Pizza Regina".to_string(),
+                "Spaghetti".to_string(),
+                "Wine".to_string(),
+            ],
+        )?;
+        // Dropping a partially consumed stream releases the server-side cursor
+        let cursor: QueryCursor<Dish> = Query::new("Dish")
+            .call_in_batches(&connection, 1)
+            .await
+            .unwrap();
+        let first = cursor.into_stream().next().await;
+        common::expect_assert(first.unwrap().is_ok())?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn projection_request() -> Result<(), String> {
+        #[derive(Deserialize)]
+        struct DishName {
+            name: String,
+        }
+
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let names: Vec<DishName> = Query::new("Dish")
+            .filter(compare!(field "name").like("Pizza%").into())
+            .select(&["name"])
+            .call_projection(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(
+            names.iter().map(|o| o.name.as_str()).collect(),
+            vec!["Pizza Mozarella", "Pizza Regina"],
+        )?;
+        let names: Vec<String> = Query::new("Dish")
+            .sort("name", None)
+            .return_expr("a.name")
+            .call_projection(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(
+            names,
+            vec![
+                "Ice Cream".to_string(),
+                "Pizza Mozarella".to_string(),
+                "Pizza Regina".to_string(),
+                "Spaghetti".to_string(),
+                "Wine".to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn collect_request() -> Result<(), String> {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct DishCount {
+            pizza: bool,
+            count: usize,
+        }
+
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let counts: Vec<DishCount> = Query::new("Dish")
+            .collect(
+                Collect::new()
+                    .group_by("pizza", "name LIKE 'Pizza%'")
+                    .count_into("count"),
+            )
+            .sort("pizza", None)
+            .call_projection(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(
+            counts,
+            vec![
+                DishCount {
+                    pizza: false,
+                    count: 3,
+                },
+                DishCount {
+                    pizza: true,
+                    count: 2,
+                },
+            ],
+        )?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn count_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        common::expect_assert_eq(Query::new("Dish").count(&connection).await.unwrap(), 5)?;
+        let query = Dish::query().filter(compare!(field "name").like("Pizza%").into());
+        common::expect_assert_eq(query.count(&connection).await.unwrap(), 2)?;
+        common::expect_assert_eq(Dish::count(&query, &connection).await.unwrap(), 2)?;
+        let query = Query::new("Dish").limit(3, Some(1));
+        common::expect_assert_eq(query.count(&connection).await.unwrap(), 3)?;
+        let query = Query::new("Dish").join_outbound(1, 1, false, PartOf::query());
+        common::expect_assert_eq(query.count(&connection).await.unwrap(), 9)?;
+        let query = Query::new("Dish").join_outbound(1, 1, false, PartOf::query().distinct());
+        common::expect_assert_eq(query.count(&connection).await.unwrap(), 3)?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn update_all_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query = Dish::query().filter(compare!(field "name").like("Pizza%").into());
+        let patch = serde_json::json!({ "name": "Pizza" });
+        common::expect_assert_eq(query.update_all(&patch, &connection).await.unwrap(), 2)?;
+        let query = Dish::query().filter(compare!(field "name").equals_str("Pizza").into());
+        common::expect_assert_eq(query.count(&connection).await.unwrap(), 2)?;
+        let patch = serde_json::json!({ "name": "Margherita" });
+        let res: QueryResult<Dish> = query
+            .clone()
+            .limit(1, None)
+            .update_all_returning(&patch, &connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(res.len(), 1)?;
+        common::expect_assert_eq(res[0].name.as_str(), "Margherita")?;
+        common::expect_assert_eq(
+            Dish::update_all(&query, &patch, &connection).await.unwrap(),
+            1,
+        )?;
+        common::expect_assert_eq(Dish::count(&query, &connection).await.unwrap(), 0)?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn remove_all_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query = Dish::query().filter(compare!(field "name").like("Pizza%").into());
+        let res: QueryResult<Dish> = query.remove_all_returning(&connection).await.unwrap();
+        common::expect_assert_eq(res.len(), 2)?;
+        common::expect_assert_eq(Dish::query().count(&connection).await.unwrap(), 3)?;
+        let query = Dish::query().sort("name", None).limit(2, None);
+        common::expect_assert_eq(query.remove_all(&connection).await.unwrap(), 2)?;
+        common::expect_assert_eq(
+            Dish::remove_all(&Dish::query(), &connection).await.unwrap(),
+            1,
+        )?;
+        common::expect_assert_eq(Dish::query().count(&connection).await.unwrap(), 0)?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn bulk_operations_refuse_complex_queries() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query = Query::new("Dish").join_outbound(1, 1, false, PartOf::query());
+        common::expect_assert(query.remove_all(&connection).await.is_err())?;
+        let query = Dish::query().select(&["name"]);
+        let patch = serde_json::json!({ "name": "Pizza" });
+        common::expect_assert(query.update_all(&patch, &connection).await.is_err())?;
+        common::expect_assert_eq(Dish::query().count(&connection).await.unwrap(), 5)?;
+        Ok(())
+    }
+
+    #[maybe_async::maybe_async]
+    async fn find_dish(name: &str, db_connection: &DatabaseConnection) -> DatabaseRecord<Dish> {
+        Dish::get(
+            &Dish::query().filter(compare!(field "name").equals_str(name).into()),
+            db_connection,
+        )
+        .await
+        .unwrap()
+        .uniq()
+        .unwrap()
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn path_requests() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let mozarella = find_dish("Pizza Mozarella", &connection).await;
+        let regina = find_dish("Pizza Regina", &connection).await;
+        let paths: Vec<Path<UndefinedRecord, PartOf>> = Query::shortest_path(
+            GraphQueryDirection::Any,
+            mozarella.id(),
+            regina.id(),
+            "PartOf",
+            false,
+        )
+        .call(&connection)
+        .await
+        .unwrap();
+        common::expect_assert_eq(paths.len(), 1)?;
+        common::expect_assert_eq(paths[0].vertices.len(), 5)?;
+        common::expect_assert_eq(paths[0].edges.len(), 4)?;
+        common::expect_assert_eq(paths[0].vertices[0].id(), mozarella.id())?;
+        common::expect_assert_eq(paths[0].vertices[4].id(), regina.id())?;
+        let paths: Vec<Path<UndefinedRecord, PartOf>> = Query::shortest_path(
+            GraphQueryDirection::Outbound,
+            mozarella.id(),
+            regina.id(),
+            "PartOf",
+            false,
+        )
+        .call(&connection)
+        .await
+        .unwrap();
+        common::expect_assert(paths.is_empty())?;
+        let paths: Vec<Path<UndefinedRecord, PartOf>> = Query::k_paths(
+            1,
+            4,
+            GraphQueryDirection::Any,
+            mozarella.id(),
+            regina.id(),
+            "PartOf",
+            false,
+        )
+        .call(&connection)
+        .await
+        .unwrap();
+        common::expect_assert_eq(paths.len(), 2)?;
+        let paths: Vec<Path<UndefinedRecord, PartOf>> = Query::k_shortest_paths(
+            GraphQueryDirection::Any,
+            mozarella.id(),
+            regina.id(),
+            "PartOf",
+            false,
+            3,
+        )
+        .call(&connection)
+        .await
+        .unwrap();
+        common::expect_assert_eq(
+            paths
+                .iter()
+                .map(|path| path.edges.len())
+                .collect::<Vec<_>>(),
+            vec![4, 4, 6],
+        )?;
+        common::expect_assert_eq(paths[2].weight, Some(6.0))?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn traversal_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let wine = find_dish("Wine", &connection).await;
+        let items: Vec<TraversalItem<Order, PartOf>> = wine
+            .outbound_query(1, 1, "PartOf")
+            .call_traversal(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(items.len(), 3)?;
+        for (order, edge, path) in &items {
+            let edge = edge.as_ref().unwrap();
+            common::expect_assert_eq(edge.id_from(), wine.id())?;
+            common::expect_assert_eq(edge.id_to(), order.id())?;
+            common::expect_assert_eq(path.edges.len(), 1)?;
+        }
+        let items: Vec<TraversalItem<UndefinedRecord, PartOf>> = wine
+            .outbound_query(0, 1, "PartOf")
+            .filter(compare!("a_edge").eq_null().into())
+            .call_traversal(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(items.len(), 1)?;
+        common::expect_assert_eq(items[0].0.id(), wine.id())?;
+        common::expect_assert(items[0].1.is_none())?;
+        common::expect_assert(items[0].2.edges.is_empty())?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn join_on_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let pairs: Vec<(DatabaseRecord<Dish>, DatabaseRecord<EdgeRecord<PartOf>>)> =
+            Query::new("Dish")
+                .filter(compare!(field "name").equals_str("Wine").into())
+                .join_on(Query::new("PartOf").return_paired(), "_id", "_from")
+                .call_projection(&connection)
+                .await
+                .unwrap();
+        common::expect_assert_eq(pairs.len(), 3)?;
+        for (dish, edge) in &pairs {
+            common::expect_assert_eq(dish.name.as_str(), "Wine")?;
+            common::expect_assert_eq(edge.id_from(), dish.id())?;
+        }
+        let orders: Vec<Order> = Query::new("PartOf")
+            .join_on(
+                Query::new("Order")
+                    .filter(compare!(field "name").like("Menu Pizza%").into())
+                    .return_merged(),
+                "_to",
+                "_id",
+            )
+            .call_projection(&connection)
+            .await
+            .unwrap();
+        // Both pizza menus have three dishes
+        common::expect_assert_eq(orders.len(), 6)?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn let_subquery_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        // Dishes part of more than one order
+        let result: QueryResult<Dish> = Query::new("Dish")
+            .let_subquery_on("orders", Query::new("PartOf"), "_id", "_from")
+            .filter(compare!("LENGTH(orders)").greater_than(1).into())
+            .sort("name", None)
+            .call(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(
+            result.iter().map(|dish| dish.name.as_str()).collect(),
+            vec!["Ice Cream", "Wine"],
+        )?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn sample_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let result: QueryResult<Dish> = Query::new("Dish")
+            .sample(3)
+            .call(&connection)
+            .await
+            .unwrap();
+        common::expect_assert_eq(result.len(), 3)?;
+        let mut names: Vec<&str> = result.iter().map(|dish| dish.name.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+        common::expect_assert_eq(names.len(), 3)?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn aql_macro_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let result: QueryResult<Dish> = aragog::aql!(
+            "FOR d IN Dish FILTER LIKE(d.name, @pattern) SORT d.name RETURN d",
+            pattern = "Pizza%",
+        )
+        .call(&connection)
+        .await
+        .unwrap();
+        common::expect_assert_eq(
+            result.iter().map(|dish| dish.name.as_str()).collect(),
+            vec!["Pizza Mozarella", "Pizza Regina"],
+        )?;
+        // The raw query is iterated as a sub query once customized
+        let result: QueryResult<Dish> = aragog::aql!(
+            "FOR d IN Dish FILTER LIKE(d.name, @pattern) SORT d.name RETURN d",
+            pattern = "Pizza%",
+        )
+        .filter(compare!(field "name").equals_str("Pizza Regina").into())
+        .call(&connection)
+        .await
+        .unwrap();
+        common::expect_assert_eq(result.len(), 1)?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn paginated_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let mut names = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let pagination = Pagination::new("name", SortDirection::Asc, 2)
+                .after(token.as_deref())
+                .with_total();
+            let page: Page<Dish> = Query::new("Dish")
+                .call_page(&pagination, &connection)
+                .await
+                .unwrap();
+            common::expect_assert_eq(page.total, Some(5))?;
+            common::expect_assert(page.items.len() <= 2)?;
+            names.extend(page.items.into_iter().map(|dish| dish.record.name));
+            token = page.next_token;
+            if token.is_none() {
+                break;
+            }
+        }
+        common::expect_assert_eq(
+            names,
+            vec![
+                "Ice Cream".to_string(),
+                "Pizza Mozarella".to_string(),
+                "Pizza Regina".to_string(),
+                "Spaghetti".to_string(),
+                "Wine".to_string(),
+            ],
+        )?;
+        let page: Page<Dish> = Query::new("Dish")
+            .call_page(
+                &Pagination::new("name", SortDirection::Desc, 10),
+                &connection,
+            )
+            .await
+            .unwrap();
+        common::expect_assert_eq(page.items.len(), 5)?;
+        common::expect_assert_eq(page.items[0].name.as_str(), "Wine")?;
+        common::expect_assert(page.next_token.is_none())?;
+        common::expect_assert(page.total.is_none())?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn request_with_options() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let query = Query::new("Dish").options(
+            QueryOptions::new()
+                .max_runtime(Duration::from_secs(10))
+                .fail_on_warning(true)
+                .cache(false)
+                .optimizer_rules(&["-all", "+use-indexes"]),
+        );
+        let res = query.raw_call(&connection).await.unwrap();
+        common::expect_assert_eq(res.len(), 5)?;
+        let count = query.count(&connection).await.unwrap();
+        common::expect_assert_eq(count, 5)?;
+        let cursor = query.raw_call_in_batches(&connection, 2).await.unwrap();
+        common::expect_assert_eq(cursor.full_count(), Some(5))?;
+        // The query can't fit in a single byte
+        let query = Query::new("Dish").options(QueryOptions::new().memory_limit(1));
+        common::expect_assert(query.raw_call(&connection).await.is_err())?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn explain_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let plan = Query::new("Dish")
+            .filter(Comparison::field("name").equals_str("Pizza Regina").into())
+            .explain(&connection)
+            .await
+            .unwrap();
+        common::expect_assert(plan.uses_index("by_name"))?;
+        common::expect_assert(plan.full_scans().is_empty())?;
+        common::expect_assert(plan.estimated_cost > 0.0)?;
+        let plan = Query::new("Dish")
+            .filter(Comparison::field("price").greater_than(10).into())
+            .explain(&connection)
+            .await
+            .unwrap();
+        common::expect_assert(!plan.uses_index("by_name"))?;
+        common::expect_assert_eq(plan.full_scans(), vec!["Dish"])?;
+        common::expect_assert(
+            Query::new("Dish")
+                .return_expr("UNKNOWN_FUNCTION(a)")
+                .explain(&connection)
+                .await
+                .is_err(),
+        )?;
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        any(feature = "blocking"),
+        async(all(not(feature = "blocking")), tokio::test)
+    )]
+    async fn profile_request() -> Result<(), String> {
+        let connection = common::setup_db().await;
+        factory(&connection).await;
+        let profile = Query::new("Dish")
+            .filter(Comparison::field("name").equals_str("Pizza Regina").into())
+            .profile(&connection)
+            .await
+            .unwrap();
+        common::expect_assert(profile.plan.uses_index("by_name"))?;
+        common::expect_assert_eq(profile.stats.scanned_full, 0)?;
+        common::expect_assert_eq(profile.stats.scanned_index, 1)?;
+        common::expect_assert(!profile.phases.is_empty())?;
+        let profile = Query::new("Dish").profile(&connection).await.unwrap();
+        common::expect_assert_eq(profile.stats.scanned_full, 5)?;
+        Ok(())
+    }
+
     #[maybe_async::test(feature = "blocking")]
     fn cursor_iterator() -> Result<(), String> {
         let connection = common::setup_db();
@@ -162,6 +162,32 @@ mod write {
                 Ok(())
             }
 
+            #[maybe_async::test(
+                feature = "blocking",
+                async(all(not(feature = "blocking")), tokio::test)
+            )]
+            async fn create_many_before_create_hook() -> Result<(), String> {
+                let connection = common::setup_db().await;
+                let mut menu = init_menu(&connection).await;
+                let invalid_dish = Dish {
+                    name: "dish".to_string(),
+                    description: "description".to_string(),
+                    price: 0,
+                    menu_id: menu.key().clone(),
+                };
+                let res = Dish::create_many(vec![invalid_dish, init_dish(menu.key())], &connection)
+                    .await
+                    .unwrap();
+                assert_eq!(res.created.len(), 1);
+                assert_eq!(res.created[0].name, "Quiche");
+                assert_eq!(res.failed.len(), 1);
+                assert_eq!(res.failed[0].0, 0);
+                assert_eq!(res.failed[0].1.http_code(), 400);
+                menu.reload_mut(&connection).await.unwrap();
+                assert_eq!(menu.dish_count, 1);
+                Ok(())
+            }
+
             #[maybe_async::test(
                 feature = "blocking",
                 async(all(not(feature = "blocking")), tokio::test)
@@ -564,7 +590,7 @@ mod read {
                     format!(
                         "\
                         FOR a in 2..5 OUTBOUND \'{}\' edges \
-                            FILTER a.price > 10 \
+                            FILTER a.price > @aragog_0 \
                             SORT a._id ASC \
                             return a\
                                 ",
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use aragog::query::{Comparison, Query, QueryResult};
-use aragog::{DatabaseRecord, ForeignLink, Link, Record};
+use aragog::{DatabaseConnection, DatabaseRecord, ForeignLink, Link, Record};
 use std::borrow::Borrow;
 
 mod common;
@@ -41,6 +41,27 @@ impl Link<Dish> for Dish {
     }
 }
 
+impl Link<Dish> for DatabaseRecord<Order> {
+    fn link_query(&self) -> Query {
+        Dish::query().filter(Comparison::field("order_id").equals_str(self.key()).into())
+    }
+}
+
+#[maybe_async::maybe_async]
+async fn create_dish(name: &str, order_id: &str, connection: &DatabaseConnection) {
+    DatabaseRecord::create(
+        Dish {
+            name: name.to_string(),
+            description: "preload Test".to_string(),
+            price: 10,
+            order_id: order_id.to_string(),
+        },
+        connection,
+    )
+    .await
+    .unwrap();
+}
+
 #[maybe_async::test(
     any(feature = "blocking"),
     async(all(not(feature = "blocking")), tokio::test)
@@ -102,3 +123,80 @@ async fn foreign_key_relation_work() -> Result<(), String> {
     common::expect_assert_eq(relation.key(), order.key())?;
     Ok(())
 }
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn preload_works() -> Result<(), String> {
+    let connection = common::setup_db().await;
+    let order = DatabaseRecord::create(
+        Order {
+            name: "Test".to_string(),
+        },
+        &connection,
+    )
+    .await
+    .unwrap();
+    create_dish("Dish 1", order.key(), &connection).await;
+    create_dish("Dish 2", order.key(), &connection).await;
+    create_dish("Orphan", "unknown", &connection).await;
+
+    let dishes: QueryResult<Dish> = Dish::query()
+        .sort("name", None)
+        .call(&connection)
+        .await
+        .unwrap();
+    let preloaded = dishes.preload::<Order, _>(&connection).await.unwrap();
+    common::expect_assert_eq(preloaded.len(), 3)?;
+    common::expect_assert_eq(
+        preloaded[0].1.as_ref().map(DatabaseRecord::key),
+        Some(order.key()),
+    )?;
+    common::expect_assert_eq(
+        preloaded[1].1.as_ref().map(DatabaseRecord::key),
+        Some(order.key()),
+    )?;
+    common::expect_assert(preloaded[2].1.is_none())?;
+    Ok(())
+}
+
+#[maybe_async::test(
+    any(feature = "blocking"),
+    async(all(not(feature = "blocking")), tokio::test)
+)]
+async fn preload_links_works() -> Result<(), String> {
+    let connection = common::setup_db().await;
+    let order = DatabaseRecord::create(
+        Order {
+            name: "Test".to_string(),
+        },
+        &connection,
+    )
+    .await
+    .unwrap();
+    let other_order = DatabaseRecord::create(
+        Order {
+            name: "Other Test".to_string(),
+        },
+        &connection,
+    )
+    .await
+    .unwrap();
+    create_dish("Dish 1", order.key(), &connection).await;
+    create_dish("Dish 2", order.key(), &connection).await;
+    create_dish("Dish 3", other_order.key(), &connection).await;
+
+    let orders: QueryResult<Order> = Order::query()
+        .sort("name", None)
+        .call(&connection)
+        .await
+        .unwrap();
+    let preloaded = orders.preload_links::<Dish, _>(&connection).await.unwrap();
+    common::expect_assert_eq(preloaded.len(), 2)?;
+    common::expect_assert_eq(preloaded[0].0.key(), other_order.key())?;
+    common::expect_assert_eq(preloaded[0].1.len(), 1)?;
+    common::expect_assert_eq(preloaded[1].0.key(), order.key())?;
+    common::expect_assert_eq(preloaded[1].1.len(), 2)?;
+    Ok(())
+}
@@ -271,9 +271,15 @@ mod safe_execute {
                 .await
                 .unwrap();
             assert_eq!(res.len(), 0);
+            let count = User::count(&query, transaction.database_connection())
+                .await
+                .unwrap();
+            assert_eq!(count, 0);
             transaction.commit().await.unwrap();
             let res = User::get(&query, &db_connection).await.unwrap();
             assert_eq!(res.len(), 1);
+            let count = User::count(&query, &db_connection).await.unwrap();
+            assert_eq!(count, 1);
             Ok(())
         }
     }